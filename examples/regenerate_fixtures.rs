@@ -0,0 +1,429 @@
+// Regenerates tests/fixtures/1.2 from a live Appwrite server.
+//
+// Point it at a throwaway project through the usual environment variables (or a .env file):
+//
+//     APPWRITE_ENDPOINT=http://localhost/v1 APPWRITE_PROJECT_ID=... APPWRITE_API_KEY=... \
+//         cargo run --example regenerate_fixtures
+//
+// The key needs every scope. Everything the tool creates is deleted again before it exits.
+// Fixtures it can't produce, like sessions from an OAuth provider, live in tests/fixtures/manual.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context};
+use appwrite::{
+    client::{AppWriteClient, RequestData},
+    config::ClientConfig,
+    prelude::*,
+};
+use reqwest::Method;
+use serde_json::{json, Value};
+
+const ID: &str = "fixtures";
+const PASSWORDLESS_ID: &str = "fixtures-passwordless";
+const UNSCHEDULED_ID: &str = "fixtures-unscheduled";
+const EMAIL: &str = "fixtures@example.com";
+const PASSWORD: &str = "fixtures-password";
+
+struct Fixtures {
+    client: AppWriteClient,
+    dir: PathBuf,
+}
+
+impl Fixtures {
+    async fn fetch(&self, method: Method, path: &str, data: RequestData) -> anyhow::Result<Value> {
+        fetch(&self.client, method, path, data).await
+    }
+
+    fn save(&self, name: &str, mut value: Value) -> anyhow::Result<Value> {
+        redact(&mut value);
+        let path = self.dir.join(format!("{}.json", name));
+        std::fs::write(&path, serde_json::to_string_pretty(&value)? + "\n")?;
+        println!("wrote {}", path.display());
+        Ok(value)
+    }
+
+    async fn save_get(&self, name: &str, path: &str) -> anyhow::Result<Value> {
+        let value = self.fetch(Method::GET, path, RequestData::None).await?;
+        self.save(name, value)
+    }
+
+    async fn save_first(&self, name: &str, path: &str, key: &str) -> anyhow::Result<Value> {
+        let list = self.fetch(Method::GET, path, RequestData::None).await?;
+        let first = list[key]
+            .get(0)
+            .cloned()
+            .with_context(|| format!("{} returned no {}", path, key))?;
+        self.save(name, first)
+    }
+
+    // Attributes, indexes, deployments and executions are processed in the background.
+    async fn wait_for(&self, path: &str, done: impl Fn(&Value) -> bool) -> anyhow::Result<Value> {
+        for _ in 0..60 {
+            let value = self.fetch(Method::GET, path, RequestData::None).await?;
+            if done(&value) {
+                return Ok(value);
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        bail!("timed out waiting for {}", path)
+    }
+}
+
+async fn fetch(
+    client: &AppWriteClient,
+    method: Method,
+    path: &str,
+    data: RequestData,
+) -> anyhow::Result<Value> {
+    let response = client.call(method.clone(), path, data).await?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        bail!("{} {} failed with {}: {}", method, path, status, text);
+    }
+    if text.is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_str(&text)?)
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match key.as_str() {
+                    "secret" | "providerAccessToken" | "providerRefreshToken" => {
+                        if value.as_str().is_some_and(|s| !s.is_empty()) {
+                            *value = json!("[REDACTED]");
+                        }
+                    }
+                    _ => redact(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    let config = ClientConfig::from_env()?;
+    let client = config.clone().into_builder()?.build()?;
+    let guest = ClientConfig {
+        api_key: None,
+        ..config
+    }
+    .into_builder()?
+    .build()?;
+    let fixtures = Fixtures {
+        client,
+        dir: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/1.2"),
+    };
+    std::fs::create_dir_all(&fixtures.dir)?;
+
+    let result = generate(&fixtures, &guest).await;
+    cleanup(&fixtures).await;
+    result
+}
+
+async fn generate(fixtures: &Fixtures, guest: &AppWriteClient) -> anyhow::Result<()> {
+    let f = fixtures;
+    let post =
+        |path: &'static str, body: Value| f.fetch(Method::POST, path, RequestData::Json(body));
+
+    f.save(
+        "database",
+        post(
+            "/databases",
+            json!({ "databaseId": ID, "name": "Fixtures" }),
+        )
+        .await?,
+    )?;
+    post(
+        "/databases/fixtures/collections",
+        json!({
+            "collectionId": ID,
+            "name": "Fixtures",
+            "permissions": ["read(\"any\")"],
+            "documentSecurity": true,
+        }),
+    )
+    .await?;
+    let base = "/databases/fixtures/collections/fixtures/attributes";
+    let common = |key: &str| json!({ "key": key, "required": false, "array": false });
+    let attributes = [
+        ("boolean", json!({ "default": true })),
+        ("integer", json!({ "min": 0, "max": 100, "default": 0 })),
+        ("float", json!({ "min": 0.0, "max": 1.0 })),
+        ("string", json!({ "size": 128 })),
+        ("email", json!({ "default": "hello@example.com" })),
+        (
+            "enum",
+            json!({ "elements": ["draft", "published"], "default": "draft" }),
+        ),
+        ("url", json!({})),
+        ("ip", json!({})),
+        ("datetime", json!({})),
+    ];
+    for (kind, extra) in attributes {
+        let mut body = common(kind);
+        body.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        let path = format!("{}/{}", base, kind);
+        let attribute = f
+            .fetch(Method::POST, &path, RequestData::Json(body))
+            .await?;
+        f.save(&format!("attribute_{}", kind), attribute)?;
+    }
+    let collection_path = "/databases/fixtures/collections/fixtures";
+    f.wait_for(collection_path, |collection| {
+        collection["attributes"]
+            .as_array()
+            .is_some_and(|attributes| attributes.iter().all(|a| a["status"] == "available"))
+    })
+    .await?;
+    post(
+        "/databases/fixtures/collections/fixtures/indexes",
+        json!({ "key": "by_string", "type": "key", "attributes": ["string"], "orders": ["ASC"] }),
+    )
+    .await?;
+    let index = f
+        .wait_for(
+            "/databases/fixtures/collections/fixtures/indexes/by_string",
+            |index| index["status"] == "available",
+        )
+        .await?;
+    f.save("index", index)?;
+    f.save_get("collection", collection_path).await?;
+    f.save(
+        "document",
+        post(
+            "/databases/fixtures/collections/fixtures/documents",
+            json!({
+                "documentId": ID,
+                "data": { "string": "Hello", "integer": 3 },
+                "permissions": ["read(\"any\")", "update(\"users\")"],
+            }),
+        )
+        .await?,
+    )?;
+    f.save_get(
+        "document_list",
+        "/databases/fixtures/collections/fixtures/documents",
+    )
+    .await?;
+
+    post(
+        "/storage/buckets",
+        json!({ "bucketId": ID, "name": "Fixtures", "permissions": ["read(\"any\")"] }),
+    )
+    .await?;
+    f.save_get("bucket", "/storage/buckets/fixtures").await?;
+    let input = InputFile::from_bytes(
+        b"fixture".to_vec(),
+        Some("fixture.txt".into()),
+        Some("text/plain".into()),
+    )
+    .await?;
+    let bucket_id = BucketId::new(ID.to_string());
+    StoragesService::create_file(
+        &f.client,
+        &bucket_id,
+        &FileId::new(ID.to_string()),
+        input,
+        &[],
+    )
+    .await?;
+    f.save_get("file", "/storage/buckets/fixtures/files/fixtures")
+        .await?;
+
+    post(
+        "/users",
+        json!({ "userId": ID, "email": EMAIL, "password": PASSWORD, "name": "Fixture User" }),
+    )
+    .await?;
+    f.fetch(
+        Method::PATCH,
+        "/users/fixtures/prefs",
+        RequestData::Json(json!({ "prefs": { "theme": "dark" } })),
+    )
+    .await?;
+    f.save_get("user", "/users/fixtures").await?;
+    f.save_get("user_list", "/users").await?;
+    post(
+        "/users",
+        json!({ "userId": PASSWORDLESS_ID, "email": "passwordless@example.com" }),
+    )
+    .await?;
+    f.save_get("user_without_password", "/users/fixtures-passwordless")
+        .await?;
+
+    f.save(
+        "team",
+        post("/teams", json!({ "teamId": ID, "name": "Fixtures" })).await?,
+    )?;
+    f.save(
+        "membership",
+        post(
+            "/teams/fixtures/memberships",
+            json!({ "email": EMAIL, "roles": ["owner"], "url": "http://localhost" }),
+        )
+        .await?,
+    )?;
+
+    // Account fixtures need a real session; Appwrite hands the cookie back in X-Fallback-Cookies.
+    let response = guest
+        .call(
+            Method::POST,
+            "/account/sessions/email",
+            RequestData::Json(json!({ "email": EMAIL, "password": PASSWORD })),
+        )
+        .await?;
+    let cookies = response
+        .headers()
+        .get("x-fallback-cookies")
+        .cloned()
+        .context("no X-Fallback-Cookies header on the session response")?;
+    f.save("session_email", response.json().await?)?;
+    let account = guest.with_header("x-fallback-cookies", cookies);
+    // Invites sent by a member stay pending until accepted, unlike those made with an API key.
+    let pending = f.save(
+        "membership_pending",
+        fetch(
+            &account,
+            Method::POST,
+            "/teams/fixtures/memberships",
+            RequestData::Json(
+                json!({ "email": "pending@example.com", "roles": [], "url": "http://localhost" }),
+            ),
+        )
+        .await?,
+    )?;
+    let invitee = format!("/users/{}", pending["userId"].as_str().unwrap_or_default());
+    f.fetch(Method::DELETE, &invitee, RequestData::None).await?;
+    f.save(
+        "account",
+        fetch(&account, Method::GET, "/account", RequestData::None).await?,
+    )?;
+    let logs = fetch(&account, Method::GET, "/account/logs", RequestData::None).await?;
+    f.save("log", logs["logs"][0].clone())?;
+    f.save(
+        "token",
+        fetch(
+            &account,
+            Method::POST,
+            "/account/verification",
+            RequestData::Json(json!({ "url": "http://localhost" })),
+        )
+        .await?,
+    )?;
+
+    post(
+        "/functions",
+        json!({
+            "functionId": ID,
+            "name": "Fixtures",
+            "execute": ["any"],
+            "runtime": "python-3.9",
+            "schedule": "0 0 * * *",
+        }),
+    )
+    .await?;
+    f.save(
+        "variable",
+        post(
+            "/functions/fixtures/variables",
+            json!({ "key": "GREETING", "value": "hello" }),
+        )
+        .await?,
+    )?;
+    let source = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/source.tar.gz");
+    let deployment = FunctionsService::create_deployment(
+        &f.client,
+        &FunctionId::new(ID.to_string()),
+        InputFile::from_path(source).await?,
+        "main.py".to_string(),
+        true,
+    )
+    .await?;
+    let deployment = f
+        .wait_for(
+            &format!("/functions/fixtures/deployments/{}", deployment.id),
+            |deployment| deployment["status"] == "ready",
+        )
+        .await?;
+    f.save("deployment", deployment)?;
+    f.save_get("function", "/functions/fixtures").await?;
+    post(
+        "/functions",
+        json!({
+            "functionId": UNSCHEDULED_ID,
+            "name": "Unscheduled",
+            "execute": ["any"],
+            "runtime": "python-3.9",
+        }),
+    )
+    .await?;
+    f.save_get("function_unscheduled", "/functions/fixtures-unscheduled")
+        .await?;
+    let execution = post("/functions/fixtures/executions", json!({})).await?;
+    let execution = f
+        .wait_for(
+            &format!(
+                "/functions/fixtures/executions/{}",
+                execution["$id"].as_str().unwrap_or_default()
+            ),
+            |execution| execution["status"] == "completed" || execution["status"] == "failed",
+        )
+        .await?;
+    f.save("execution", execution)?;
+    f.save_first("runtime", "/functions/runtimes", "runtimes")
+        .await?;
+
+    f.save_get("health", "/health").await?;
+    f.save_get("health_time", "/health/time").await?;
+    f.save_get("health_queue", "/health/queue/webhooks").await?;
+    f.save_get("health_antivirus", "/health/anti-virus").await?;
+
+    f.save_get("locale", "/locale").await?;
+    f.save_first("country", "/locale/countries", "countries")
+        .await?;
+    f.save_first("phone", "/locale/countries/phones", "phones")
+        .await?;
+    f.save_first("continent", "/locale/continents", "continents")
+        .await?;
+    f.save_first("currency", "/locale/currencies", "currencies")
+        .await?;
+    f.save_first("language", "/locale/languages", "languages")
+        .await?;
+
+    let response = f
+        .client
+        .call(Method::GET, "/databases/missing", RequestData::None)
+        .await?;
+    f.save("error", response.json().await?)?;
+    Ok(())
+}
+
+async fn cleanup(fixtures: &Fixtures) {
+    for path in [
+        "/databases/fixtures",
+        "/storage/buckets/fixtures",
+        "/teams/fixtures",
+        "/users/fixtures",
+        "/users/fixtures-passwordless",
+        "/functions/fixtures",
+        "/functions/fixtures-unscheduled",
+    ] {
+        if let Err(error) = fixtures
+            .fetch(Method::DELETE, path, RequestData::None)
+            .await
+        {
+            eprintln!("failed to clean up {}: {}", path, error);
+        }
+    }
+}
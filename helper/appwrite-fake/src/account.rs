@@ -0,0 +1,483 @@
+use hyper::{Method, StatusCode};
+use serde_json::{json, Value};
+
+use crate::{
+    query,
+    request::{later, now, route_not_found, unique_id, Handled, Request, Response},
+    state::{field_is, has_id, State},
+    users,
+};
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Handled {
+    let segments = request.segments();
+    // Sign-up, login and recovery are the only account flows that work without being signed in.
+    match (&request.method, segments.as_slice()) {
+        (&Method::POST, ["account"]) => {
+            let user = users::insert_user(
+                state,
+                &request.require("userId")?,
+                Some(request.require("email")?),
+                None,
+                Some(request.require("password")?),
+                request.string("name"),
+            )?;
+            return Response::created(user);
+        }
+        (&Method::POST, ["account", "sessions", "email"]) => {
+            let email = request.require("email")?.to_lowercase();
+            let user = state
+                .users
+                .iter()
+                .find(|user| field_is(user, "email", &email))
+                .and_then(|user| user["$id"].as_str())
+                .map(str::to_string)
+                .ok_or_else(invalid_credentials)?;
+            check_password(state, &user, request.string("password"))?;
+            return create_session(state, &user, &email);
+        }
+        (&Method::POST, ["account", "recovery"]) => {
+            let email = request.require("email")?.to_lowercase();
+            request.require("url")?;
+            let user = state
+                .users
+                .iter()
+                .find(|user| field_is(user, "email", &email))
+                .and_then(|user| user["$id"].as_str())
+                .map(str::to_string)
+                .ok_or_else(users::user_not_found)?;
+            return Response::created(create_token(state, &user, "recovery"));
+        }
+        (&Method::PUT, ["account", "recovery"]) => {
+            let user = request.require("userId")?;
+            let token = consume_token(state, &user, &request.require("secret")?, "recovery")?;
+            let password = request.require("password")?;
+            if request.string("passwordAgain").as_deref() != Some(password.as_str()) {
+                return Err(Response::invalid(
+                    "general_argument_invalid",
+                    "Passwords must match.",
+                ));
+            }
+            users::update_password(state, &user, &password)?;
+            return Response::ok(token);
+        }
+        _ => {}
+    }
+
+    let user = authenticate(state, request)?;
+    match (&request.method, segments.as_slice()) {
+        (&Method::GET, ["account"]) => Response::ok(users::get_user(state, &user)?.clone()),
+        (&Method::GET, ["account", "prefs"]) => Response::ok(
+            state
+                .user_prefs
+                .get(&user)
+                .cloned()
+                .unwrap_or_else(|| json!({})),
+        ),
+        (&Method::PATCH, ["account", "prefs"]) => {
+            let prefs = request.json("prefs").cloned().unwrap_or_else(|| json!({}));
+            users::update_prefs(state, &user, prefs)
+        }
+        (&Method::PATCH, ["account", "name"]) => {
+            users::update_user(state, &user, "name", json!(request.require("name")?))
+        }
+        (&Method::PATCH, ["account", "password"]) => {
+            if state.passwords.contains_key(&user) {
+                check_password(state, &user, request.string("oldPassword"))?;
+            }
+            users::update_password(state, &user, &request.require("password")?)
+        }
+        (&Method::PATCH, ["account", "email"]) => {
+            check_password(state, &user, request.string("password"))?;
+            users::update_email(state, &user, &request.require("email")?)
+        }
+        (&Method::PATCH, ["account", "phone"]) => {
+            check_password(state, &user, request.string("password"))?;
+            users::update_user(state, &user, "phone", json!(request.require("number")?))
+        }
+        (&Method::PATCH, ["account", "status"]) => {
+            state.jwts.retain(|_, id| *id != user);
+            state
+                .sessions
+                .retain(|item| !field_is(item, "userId", &user));
+            users::update_user(state, &user, "status", json!(false))
+        }
+        (&Method::GET, ["account", "sessions"]) => {
+            let items = state
+                .sessions
+                .iter()
+                .filter(|item| field_is(item, "userId", &user))
+                .cloned()
+                .collect();
+            query::list("sessions", items, request, &[])
+        }
+        (&Method::DELETE, ["account", "sessions"]) => {
+            state
+                .sessions
+                .retain(|item| !field_is(item, "userId", &user));
+            Ok(clear_session_cookies(state, Response::no_content()?))
+        }
+        (&Method::GET, ["account", "sessions", session]) => {
+            let session = resolve_session(state, request, session);
+            Response::ok(find_session(state, &user, &session)?)
+        }
+        (&Method::PATCH, ["account", "sessions", session]) => {
+            let session = resolve_session(state, request, session);
+            Response::ok(find_session(state, &user, &session)?)
+        }
+        (&Method::DELETE, ["account", "sessions", session]) => {
+            let current = current_session(state, request);
+            let session = find_session(state, &user, &resolve_session(state, request, session))?;
+            let session = session["$id"].as_str().unwrap_or_default().to_string();
+            state.sessions.retain(|item| !has_id(item, &session));
+            state.session_secrets.retain(|_, id| *id != session);
+            match current {
+                Some(current) if current == session => {
+                    Ok(clear_session_cookies(state, Response::no_content()?))
+                }
+                _ => Response::no_content(),
+            }
+        }
+        (&Method::GET, ["account", "logs"]) => Response::ok(json!({ "logs": [], "total": 0 })),
+        (&Method::POST, ["account", "verification"]) => {
+            request.require("url")?;
+            Response::created(create_token(state, &user, "verification"))
+        }
+        (&Method::PUT, ["account", "verification"]) => {
+            let token = consume_token(
+                state,
+                &request.require("userId")?,
+                &request.require("secret")?,
+                "verification",
+            )?;
+            users::update_user(state, &user, "emailVerification", json!(true))?;
+            Response::ok(token)
+        }
+        (&Method::POST, ["account", "verification", "phone"]) => {
+            Response::created(create_token(state, &user, "phone"))
+        }
+        (&Method::PUT, ["account", "verification", "phone"]) => {
+            let token = consume_token(
+                state,
+                &request.require("userId")?,
+                &request.require("secret")?,
+                "phone",
+            )?;
+            users::update_user(state, &user, "phoneVerification", json!(true))?;
+            Response::ok(token)
+        }
+        _ => route_not_found(),
+    }
+}
+
+fn unauthorized() -> Response {
+    Response::error(
+        StatusCode::UNAUTHORIZED,
+        "general_unauthorized_scope",
+        "User (role: guests) missing scope (account)",
+    )
+}
+
+fn authenticate(state: &State, request: &Request) -> Result<String, Response> {
+    let user = request
+        .header("x-appwrite-jwt")
+        .and_then(|jwt| state.jwts.get(jwt))
+        .cloned()
+        .or_else(|| {
+            let session = current_session(state, request)?;
+            state
+                .sessions
+                .iter()
+                .find(|item| has_id(item, &session))
+                .and_then(|item| item["userId"].as_str())
+                .map(str::to_string)
+        })
+        .ok_or_else(unauthorized)?;
+    match users::get_user(state, &user) {
+        Ok(record) if record["status"].as_bool() == Some(true) => Ok(user),
+        _ => Err(unauthorized()),
+    }
+}
+
+fn invalid_credentials() -> Response {
+    Response::error(
+        StatusCode::UNAUTHORIZED,
+        "user_invalid_credentials",
+        "Invalid credentials. Please check the email and password.",
+    )
+}
+
+fn check_password(state: &State, user: &str, password: Option<String>) -> Result<(), Response> {
+    if password.is_some() && state.passwords.get(user) == password.as_ref() {
+        Ok(())
+    } else {
+        Err(invalid_credentials())
+    }
+}
+
+fn cookie_name(state: &State) -> String {
+    format!("a_session_{}", state.project_id)
+}
+
+// Like Appwrite, the secret is read from the session cookie first and `X-Fallback-Cookies` second.
+fn current_session(state: &State, request: &Request) -> Option<String> {
+    let name = cookie_name(state);
+    let from_cookie = request.header("cookie").and_then(|cookies| {
+        cookies
+            .split(';')
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, secret)| secret.to_string())
+    });
+    let secret = from_cookie.or_else(|| {
+        let fallback = request.header("x-fallback-cookies")?;
+        let cookies = serde_json::from_str::<Value>(fallback).ok()?;
+        cookies[&name].as_str().map(str::to_string)
+    })?;
+    state.session_secrets.get(&secret).cloned()
+}
+
+fn resolve_session(state: &State, request: &Request, session: &str) -> String {
+    match (session, current_session(state, request)) {
+        ("current", Some(current)) => current,
+        _ => session.to_string(),
+    }
+}
+
+fn create_session(state: &mut State, user: &str, email: &str) -> Handled {
+    let secret = unique_id();
+    let session = json!({
+        "$id": unique_id(),
+        "$createdAt": now(),
+        "userId": user,
+        "expire": later(365),
+        "provider": "email",
+        "providerUid": email,
+        "providerAccessToken": "",
+        "providerAccessTokenExpiry": "",
+        "providerRefreshToken": "",
+        "ip": "127.0.0.1",
+        "osCode": "",
+        "osName": "",
+        "osVersion": "",
+        "clientType": "",
+        "clientCode": "",
+        "clientName": "",
+        "clientVersion": "",
+        "clientEngine": "",
+        "clientEngineVersion": "",
+        "deviceName": "",
+        "deviceBrand": "",
+        "deviceModel": "",
+        "countryCode": "--",
+        "countryName": "Unknown",
+        "current": true,
+    });
+    state.sessions.push(session.clone());
+    state
+        .session_secrets
+        .insert(secret.clone(), session["$id"].as_str().unwrap().to_string());
+    let name = cookie_name(state);
+    Ok(Response::json(StatusCode::CREATED, session)
+        .with_header(
+            "set-cookie",
+            format!("{}_legacy={}; path=/; httponly", name, secret),
+        )
+        .with_header(
+            "set-cookie",
+            format!("{}={}; path=/; httponly", name, secret),
+        )
+        .with_header("x-fallback-cookies", json!({ name: secret }).to_string()))
+}
+
+fn clear_session_cookies(state: &State, response: Response) -> Response {
+    let name = cookie_name(state);
+    let expired = "expires=Thu, 01-Jan-1970 00:00:00 GMT; path=/; httponly";
+    response
+        .with_header("set-cookie", format!("{}_legacy=; {}", name, expired))
+        .with_header("set-cookie", format!("{}=; {}", name, expired))
+        .with_header("x-fallback-cookies", "[]".to_string())
+}
+
+fn find_session(state: &State, user: &str, session: &str) -> Result<Value, Response> {
+    state
+        .sessions
+        .iter()
+        .find(|item| {
+            field_is(item, "userId", user)
+                && (has_id(item, session) || (session == "current" && item["current"] == true))
+        })
+        .cloned()
+        .ok_or_else(|| {
+            Response::not_found(
+                "user_session_not_found",
+                "The current user session could not be found.",
+            )
+        })
+}
+
+// Tokens keep a `kind` so a recovery secret can't confirm a verification; it is stripped from responses.
+fn create_token(state: &mut State, user: &str, kind: &str) -> Value {
+    let token = json!({
+        "$id": unique_id(),
+        "$createdAt": now(),
+        "userId": user,
+        "secret": unique_id(),
+        "expire": later(7),
+    });
+    let mut stored = token.clone();
+    stored["kind"] = json!(kind);
+    state.tokens.push(stored);
+    token
+}
+
+fn consume_token(
+    state: &mut State,
+    user: &str,
+    secret: &str,
+    kind: &str,
+) -> Result<Value, Response> {
+    let position = state
+        .tokens
+        .iter()
+        .position(|token| {
+            field_is(token, "userId", user)
+                && field_is(token, "secret", secret)
+                && field_is(token, "kind", kind)
+        })
+        .ok_or_else(|| {
+            Response::error(
+                StatusCode::UNAUTHORIZED,
+                "user_invalid_token",
+                "Invalid token passed in the request.",
+            )
+        })?;
+    let mut token = state.tokens.remove(position);
+    token.as_object_mut().unwrap().remove("kind");
+    Ok(token)
+}
+
+#[tokio::test]
+async fn test_account_with_jwt() {
+    use appwrite::prelude::*;
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.client();
+    let user = UsersService::create_user(
+        &client,
+        UserId::new("alice".to_string()),
+        Some("alice@example.com".to_string()),
+        None,
+        Some("password".to_string()),
+        Some("Alice".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let error = AccountsService::get_account(&client).await.unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::GeneralUnauthorizedScope));
+
+    let session = client.with_jwt(&fake.create_jwt(&user.id.0)).unwrap();
+    let account = AccountsService::get_account(&session).await.unwrap();
+    assert_eq!(account.email, "alice@example.com");
+
+    let error = AccountsService::update_user_email(&session, "bob@example.com", "wrong")
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::UserInvalidCredentials));
+    let account = AccountsService::update_account_name(&session, "Alicia")
+        .await
+        .unwrap();
+    assert_eq!(account.name, "Alicia");
+}
+
+#[tokio::test]
+async fn test_account_with_session() {
+    use appwrite::prelude::*;
+    use hyper::header::HeaderValue;
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let guest = || AppWriteClient::builder(&fake.url(), fake.project_id());
+    let client = guest().build().unwrap();
+    let user = AccountsService::create_account(
+        &client,
+        &UserId::new("carol".to_string()),
+        "carol@example.com",
+        "password",
+        Some("Carol"),
+    )
+    .await
+    .unwrap();
+    let error = AccountsService::get_account(&client).await.unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::GeneralUnauthorizedScope));
+
+    let error = AccountsService::create_email_session(&client, "carol@example.com", "wrong")
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::UserInvalidCredentials));
+    assert!(client.session().is_empty());
+
+    let session = AccountsService::create_email_session(&client, "carol@example.com", "password")
+        .await
+        .unwrap();
+    assert_eq!(session.user_id, user.id);
+    let account = AccountsService::get_account(&client).await.unwrap();
+    assert_eq!(account.name, "Carol");
+
+    // The fallback header alone authenticates too, as for clients that drop cookies.
+    let fallback = client.session().fallback.unwrap();
+    let other = guest()
+        .add_header(
+            "x-fallback-cookies",
+            HeaderValue::from_str(&fallback).unwrap(),
+        )
+        .build()
+        .unwrap();
+    let current = AccountsService::get_session(&other, &SessionId::new("current".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(current.id, session.id);
+
+    AccountsService::delete_current_session(&client)
+        .await
+        .unwrap();
+    assert!(client.session().is_empty());
+    let error = AccountsService::get_account(&other).await.unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::GeneralUnauthorizedScope));
+}
+
+#[tokio::test]
+async fn test_session_survives_restart() {
+    use appwrite::{prelude::*, session::FileSessionStore};
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let path = std::env::temp_dir().join(format!("appwrite-fake-session-{}.json", unique_id()));
+    let open = || {
+        AppWriteClient::builder(&fake.url(), fake.project_id())
+            .session_store(FileSessionStore::new(&path))
+            .build()
+            .unwrap()
+    };
+    let client = open();
+    AccountsService::create_account(
+        &client,
+        &UserId::unique(),
+        "dave@example.com",
+        "password",
+        None,
+    )
+    .await
+    .unwrap();
+    AccountsService::create_email_session(&client, "dave@example.com", "password")
+        .await
+        .unwrap();
+    drop(client);
+
+    let client = open();
+    let account = AccountsService::get_account(&client).await.unwrap();
+    assert_eq!(account.email, "dave@example.com");
+    AccountsService::delete_current_session(&client)
+        .await
+        .unwrap();
+    assert!(!path.exists());
+    assert!(open().session().is_empty());
+}
@@ -0,0 +1,582 @@
+use hyper::Method;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    query,
+    request::{now, resolve_id, route_not_found, Body, Handled, Request, Response},
+    state::{field_is, find, find_mut, has_id, State},
+};
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Handled {
+    match (&request.method, request.segments().as_slice()) {
+        (&Method::POST, ["databases"]) => create_database(state, request),
+        (&Method::GET, ["databases"]) => {
+            query::list("databases", state.databases.clone(), request, &["name"])
+        }
+        (&Method::GET, ["databases", db]) => Response::ok(database(state, db)?.clone()),
+        (&Method::PUT, ["databases", db]) => {
+            let name = request.require("name")?;
+            database(state, db)?;
+            let database = find_mut(&mut state.databases, db).unwrap();
+            database["name"] = json!(name);
+            database["$updatedAt"] = json!(now());
+            Response::ok(database.clone())
+        }
+        (&Method::DELETE, ["databases", db]) => {
+            database(state, db)?;
+            state.databases.retain(|item| !has_id(item, db));
+            state
+                .collections
+                .retain(|item| !field_is(item, "databaseId", db));
+            state
+                .documents
+                .retain(|item| !field_is(item, "$databaseId", db));
+            Response::no_content()
+        }
+        (&Method::POST, ["databases", db, "collections"]) => create_collection(state, request, db),
+        (&Method::GET, ["databases", db, "collections"]) => {
+            database(state, db)?;
+            let items = state
+                .collections
+                .iter()
+                .filter(|item| field_is(item, "databaseId", db))
+                .cloned()
+                .collect();
+            query::list("collections", items, request, &["name"])
+        }
+        (&Method::GET, ["databases", db, "collections", col]) => {
+            Response::ok(collection(state, db, col)?.clone())
+        }
+        (&Method::PUT, ["databases", db, "collections", col]) => {
+            let name = request.require("name")?;
+            let collection = collection_mut(state, db, col)?;
+            collection["name"] = json!(name);
+            if let Some(permissions) = request.strings("permissions") {
+                collection["$permissions"] = json!(permissions);
+            }
+            if let Some(security) = request.bool("documentSecurity") {
+                collection["documentSecurity"] = json!(security);
+            }
+            if let Some(enabled) = request.bool("enabled") {
+                collection["enabled"] = json!(enabled);
+            }
+            collection["$updatedAt"] = json!(now());
+            Response::ok(collection.clone())
+        }
+        (&Method::DELETE, ["databases", db, "collections", col]) => {
+            collection(state, db, col)?;
+            state
+                .collections
+                .retain(|item| !(has_id(item, col) && field_is(item, "databaseId", db)));
+            state.documents.retain(|item| {
+                !(field_is(item, "$databaseId", db) && field_is(item, "$collectionId", col))
+            });
+            Response::no_content()
+        }
+        (&Method::POST, ["databases", db, "collections", col, "attributes", kind]) => {
+            create_attribute(state, request, db, col, kind)
+        }
+        (&Method::GET, ["databases", db, "collections", col, "attributes"]) => {
+            let attributes = collection(state, db, col)?["attributes"].clone();
+            Response::ok(json!({
+                "total": attributes.as_array().map(Vec::len).unwrap_or_default(),
+                "attributes": attributes,
+            }))
+        }
+        (&Method::GET, ["databases", db, "collections", col, "attributes", key]) => {
+            let attribute = collection(state, db, col)?["attributes"]
+                .as_array()
+                .and_then(|attributes| {
+                    attributes
+                        .iter()
+                        .find(|attribute| field_is(attribute, "key", key))
+                })
+                .cloned()
+                .ok_or_else(attribute_not_found)?;
+            Response::ok(attribute)
+        }
+        (&Method::DELETE, ["databases", db, "collections", col, "attributes", key]) => {
+            let attributes = collection_mut(state, db, col)?["attributes"]
+                .as_array_mut()
+                .unwrap();
+            let before = attributes.len();
+            attributes.retain(|attribute| !field_is(attribute, "key", key));
+            if attributes.len() == before {
+                return Err(attribute_not_found());
+            }
+            Response::no_content()
+        }
+        (&Method::POST, ["databases", db, "collections", col, "indexes"]) => {
+            let key = request.require("key")?;
+            let index = json!({
+                "key": key,
+                "type": request.require("type")?,
+                "status": "available",
+                "attributes": request.strings("attributes").unwrap_or_default(),
+                "orders": request.json("orders").cloned().unwrap_or_else(|| json!([])),
+            });
+            let indexes = collection_mut(state, db, col)?["indexes"]
+                .as_array_mut()
+                .unwrap();
+            if indexes.iter().any(|index| field_is(index, "key", &key)) {
+                return Err(Response::conflict(
+                    "index_already_exists",
+                    "Index already exists.",
+                ));
+            }
+            indexes.push(index.clone());
+            Response::accepted(index)
+        }
+        (&Method::GET, ["databases", db, "collections", col, "indexes"]) => {
+            let indexes = collection(state, db, col)?["indexes"].clone();
+            Response::ok(json!({
+                "total": indexes.as_array().map(Vec::len).unwrap_or_default(),
+                "indexes": indexes,
+            }))
+        }
+        (&Method::GET, ["databases", db, "collections", col, "indexes", key]) => {
+            let index = collection(state, db, col)?["indexes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|index| field_is(index, "key", key))
+                .cloned()
+                .ok_or_else(index_not_found)?;
+            Response::ok(index)
+        }
+        (&Method::DELETE, ["databases", db, "collections", col, "indexes", key]) => {
+            let indexes = collection_mut(state, db, col)?["indexes"]
+                .as_array_mut()
+                .unwrap();
+            let before = indexes.len();
+            indexes.retain(|index| !field_is(index, "key", key));
+            if indexes.len() == before {
+                return Err(index_not_found());
+            }
+            Response::no_content()
+        }
+        (&Method::POST, ["databases", db, "collections", col, "documents"]) => {
+            create_document(state, request, db, col)
+        }
+        (&Method::GET, ["databases", db, "collections", col, "documents"]) => {
+            collection(state, db, col)?;
+            let items = documents(state, db, col).cloned().collect();
+            query::list("documents", items, request, &[])
+        }
+        (&Method::GET, ["databases", db, "collections", col, "documents", doc]) => {
+            collection(state, db, col)?;
+            let document = documents(state, db, col)
+                .find(|item| has_id(item, doc))
+                .cloned()
+                .ok_or_else(document_not_found)?;
+            Response::ok(document)
+        }
+        (&Method::PATCH, ["databases", db, "collections", col, "documents", doc]) => {
+            update_document(state, request, db, col, doc)
+        }
+        (&Method::DELETE, ["databases", db, "collections", col, "documents", doc]) => {
+            collection(state, db, col)?;
+            let before = state.documents.len();
+            state.documents.retain(|item| {
+                !(has_id(item, doc)
+                    && field_is(item, "$databaseId", db)
+                    && field_is(item, "$collectionId", col))
+            });
+            if state.documents.len() == before {
+                return Err(document_not_found());
+            }
+            Response::no_content()
+        }
+        _ => route_not_found(),
+    }
+}
+
+fn database<'a>(state: &'a State, db: &str) -> Result<&'a Value, Response> {
+    find(&state.databases, db)
+        .ok_or_else(|| Response::not_found("database_not_found", "Database not found"))
+}
+
+fn collection<'a>(state: &'a State, db: &str, col: &str) -> Result<&'a Value, Response> {
+    database(state, db)?;
+    state
+        .collections
+        .iter()
+        .find(|item| has_id(item, col) && field_is(item, "databaseId", db))
+        .ok_or_else(collection_not_found)
+}
+
+fn collection_mut<'a>(
+    state: &'a mut State,
+    db: &str,
+    col: &str,
+) -> Result<&'a mut Value, Response> {
+    database(state, db)?;
+    state
+        .collections
+        .iter_mut()
+        .find(|item| has_id(item, col) && field_is(item, "databaseId", db))
+        .ok_or_else(collection_not_found)
+}
+
+fn documents<'a>(state: &'a State, db: &'a str, col: &'a str) -> impl Iterator<Item = &'a Value> {
+    state.documents.iter().filter(move |item| {
+        field_is(item, "$databaseId", db) && field_is(item, "$collectionId", col)
+    })
+}
+
+fn collection_not_found() -> Response {
+    Response::not_found(
+        "collection_not_found",
+        "Collection with the requested ID could not be found.",
+    )
+}
+
+fn document_not_found() -> Response {
+    Response::not_found(
+        "document_not_found",
+        "Document with the requested ID could not be found.",
+    )
+}
+
+fn attribute_not_found() -> Response {
+    Response::not_found("attribute_not_found", "Attribute not found in schema")
+}
+
+fn index_not_found() -> Response {
+    Response::not_found("index_not_found", "Index not found")
+}
+
+fn create_database(state: &mut State, request: &Request) -> Handled {
+    let id = resolve_id(&request.require("databaseId")?);
+    if find(&state.databases, &id).is_some() {
+        return Err(Response::conflict(
+            "database_already_exists",
+            "Database already exists",
+        ));
+    }
+    let now = now();
+    let database = json!({
+        "$id": id,
+        "name": request.require("name")?,
+        "$createdAt": now,
+        "$updatedAt": now,
+    });
+    state.databases.push(database.clone());
+    Response::created(database)
+}
+
+fn create_collection(state: &mut State, request: &Request, db: &str) -> Handled {
+    database(state, db)?;
+    let id = resolve_id(&request.require("collectionId")?);
+    if collection(state, db, &id).is_ok() {
+        return Err(Response::conflict(
+            "collection_already_exists",
+            "Collection already exists",
+        ));
+    }
+    let now = now();
+    let collection = json!({
+        "$id": id,
+        "$createdAt": now,
+        "$updatedAt": now,
+        "$permissions": request.strings("permissions").unwrap_or_default(),
+        "databaseId": db,
+        "name": request.require("name")?,
+        "enabled": true,
+        "documentSecurity": request.bool("documentSecurity").unwrap_or_default(),
+        "attributes": [],
+        "indexes": [],
+    });
+    state.collections.push(collection.clone());
+    Response::created(collection)
+}
+
+fn create_attribute(
+    state: &mut State,
+    request: &Request,
+    db: &str,
+    col: &str,
+    kind: &str,
+) -> Handled {
+    let key = request.require("key")?;
+    let (attribute_type, format) = match kind {
+        "boolean" => ("boolean", None),
+        "integer" => ("integer", None),
+        "float" => ("double", None),
+        "string" => ("string", None),
+        "datetime" => ("datetime", None),
+        "email" | "enum" | "url" | "ip" => ("string", Some(kind)),
+        _ => return route_not_found(),
+    };
+    let mut attribute = match request.body {
+        Body::Json(Value::Object(ref body)) => body.clone(),
+        _ => Map::new(),
+    };
+    attribute
+        .entry("type")
+        .or_insert_with(|| json!(attribute_type));
+    if let Some(format) = format {
+        attribute.entry("format").or_insert_with(|| json!(format));
+    }
+    attribute.entry("array").or_insert(json!(false));
+    attribute.entry("default").or_insert(Value::Null);
+    attribute.insert("status".to_string(), json!("available"));
+    let attributes = collection_mut(state, db, col)?["attributes"]
+        .as_array_mut()
+        .unwrap();
+    if attributes
+        .iter()
+        .any(|attribute| field_is(attribute, "key", &key))
+    {
+        return Err(Response::conflict(
+            "attribute_already_exists",
+            "Attribute already exists",
+        ));
+    }
+    attributes.push(Value::Object(attribute.clone()));
+    Response::accepted(Value::Object(attribute))
+}
+
+// Mirrors Appwrite's structure validator closely enough to catch typos and type mismatches in tests.
+fn validate(collection: &Value, data: &Map<String, Value>, partial: bool) -> Result<(), Response> {
+    let attributes = collection["attributes"].as_array().unwrap();
+    let invalid = |message: String| Response::invalid("document_invalid_structure", &message);
+    for key in data.keys() {
+        if !attributes
+            .iter()
+            .any(|attribute| field_is(attribute, "key", key))
+        {
+            return Err(invalid(format!(
+                "Invalid document structure: Unknown attribute: \"{}\"",
+                key
+            )));
+        }
+    }
+    for attribute in attributes {
+        let key = attribute["key"].as_str().unwrap_or_default();
+        let value = match data.get(key) {
+            None | Some(Value::Null) => {
+                if !partial && attribute["required"].as_bool().unwrap_or_default() {
+                    return Err(invalid(format!(
+                        "Invalid document structure: Missing required attribute \"{}\"",
+                        key
+                    )));
+                }
+                continue;
+            }
+            Some(value) => value,
+        };
+        let values = match (attribute["array"].as_bool().unwrap_or_default(), value) {
+            (true, Value::Array(values)) => values.clone(),
+            (false, value) if !value.is_array() => vec![value.clone()],
+            _ => {
+                return Err(invalid(format!(
+                    "Invalid document structure: Attribute \"{}\" has invalid format",
+                    key
+                )))
+            }
+        };
+        let valid = values.iter().all(|value| match attribute["type"].as_str() {
+            _ if attribute["format"] == "enum" => attribute["elements"]
+                .as_array()
+                .is_some_and(|elements| elements.contains(value)),
+            Some("boolean") => value.is_boolean(),
+            Some("integer") => value.is_i64() || value.is_u64(),
+            Some("double") => value.is_number(),
+            _ => value.is_string(),
+        });
+        if !valid {
+            return Err(invalid(format!(
+                "Invalid document structure: Attribute \"{}\" has invalid type",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn create_document(state: &mut State, request: &Request, db: &str, col: &str) -> Handled {
+    let collection = collection(state, db, col)?.clone();
+    let id = resolve_id(&request.require("documentId")?);
+    let data = match request.json("data") {
+        Some(Value::Object(data)) => data.clone(),
+        _ => {
+            return Err(Response::invalid(
+                "document_invalid_structure",
+                "Invalid document structure: data must be an object",
+            ))
+        }
+    };
+    validate(&collection, &data, false)?;
+    if documents(state, db, col).any(|item| has_id(item, &id)) {
+        return Err(Response::conflict(
+            "document_already_exists",
+            "Document with the requested ID already exists.",
+        ));
+    }
+    let mut document = Map::new();
+    for attribute in collection["attributes"].as_array().unwrap() {
+        let key = attribute["key"].as_str().unwrap_or_default();
+        let value = data
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| attribute["default"].clone());
+        document.insert(key.to_string(), value);
+    }
+    let now = now();
+    document.insert("$id".to_string(), json!(id));
+    document.insert("$collectionId".to_string(), json!(col));
+    document.insert("$databaseId".to_string(), json!(db));
+    document.insert("$createdAt".to_string(), json!(now));
+    document.insert("$updatedAt".to_string(), json!(now));
+    document.insert(
+        "$permissions".to_string(),
+        json!(request.strings("permissions").unwrap_or_default()),
+    );
+    let document = Value::Object(document);
+    state.documents.push(document.clone());
+    Response::created(document)
+}
+
+fn update_document(
+    state: &mut State,
+    request: &Request,
+    db: &str,
+    col: &str,
+    doc: &str,
+) -> Handled {
+    let collection = collection(state, db, col)?.clone();
+    let data = match request.json("data") {
+        Some(Value::Object(data)) => data.clone(),
+        _ => Map::new(),
+    };
+    validate(&collection, &data, true)?;
+    let document = state
+        .documents
+        .iter_mut()
+        .find(|item| {
+            has_id(item, doc)
+                && field_is(item, "$databaseId", db)
+                && field_is(item, "$collectionId", col)
+        })
+        .ok_or_else(document_not_found)?;
+    for (key, value) in data {
+        document[key.as_str()] = value;
+    }
+    if let Some(permissions) = request.strings("permissions") {
+        document["$permissions"] = json!(permissions);
+    }
+    document["$updatedAt"] = json!(now());
+    Response::ok(document.clone())
+}
+
+#[tokio::test]
+async fn test_databases() {
+    use appwrite::prelude::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Post {
+        title: String,
+        views: i64,
+    }
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.client();
+    let database_id = DatabaseId::new("blog".to_string());
+    let collection_id = CollectionId::new("posts".to_string());
+    DatabasesService::create_database(
+        &client,
+        CreateDatabasePayload {
+            database_id: database_id.clone(),
+            name: "Blog".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    DatabasesService::create_collection(
+        &client,
+        &database_id,
+        CreateCollectionPayload {
+            collection_id: collection_id.clone(),
+            name: "Posts".to_string(),
+            permissions: vec![],
+            document_security: false,
+        },
+    )
+    .await
+    .unwrap();
+    for attribute in [
+        Attribute::new_string("title", true, None, 128, None),
+        Attribute::new_integer("views", false, Some(0), None, None, None),
+    ] {
+        DatabasesService::create_attribute(&client, &database_id, &collection_id, attribute)
+            .await
+            .unwrap();
+    }
+    let attributes = DatabasesService::list_attributes(&client, &database_id, &collection_id)
+        .await
+        .unwrap();
+    assert_eq!(attributes.total, 2);
+    let views = DatabasesService::get_attribute(&client, &database_id, &collection_id, "views")
+        .await
+        .unwrap();
+    assert!(matches!(views, Attribute::Integer(ref attribute) if attribute.key == "views"));
+    for (title, views) in [("first", 10), ("second", 30), ("third", 20)] {
+        DatabasesService::create_document::<serde_json::Value>(
+            &client,
+            &database_id,
+            &collection_id,
+            CreateDocumentPayload {
+                document_id: DocumentId::new(title.to_string()),
+                data: json!({ "title": title, "views": views }),
+                permissions: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let error = DatabasesService::create_document::<serde_json::Value>(
+        &client,
+        &database_id,
+        &collection_id,
+        CreateDocumentPayload {
+            document_id: DocumentId::unique(),
+            data: json!({ "titel": "typo" }),
+            permissions: vec![],
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::DocumentInvalidStructure));
+
+    let posts = DatabasesService::list_documents::<Post>(
+        &client,
+        &database_id,
+        &collection_id,
+        Some(vec!["views".greater_than(15), "views".order_desc()]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(posts.total, 2);
+    let titles = posts
+        .items
+        .iter()
+        .map(|post| post.data.title.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, ["second", "third"]);
+
+    let post = DatabasesService::update_document::<Post>(
+        &client,
+        &database_id,
+        &collection_id,
+        &DocumentId::new("first".to_string()),
+        UpdateDocumentPayload {
+            data: Some(json!({ "views": 11 })),
+            permissions: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!((post.data.title.as_str(), post.data.views), ("first", 11));
+}
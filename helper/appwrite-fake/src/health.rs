@@ -0,0 +1,59 @@
+use hyper::Method;
+use serde_json::json;
+
+use crate::{
+    request::{route_not_found, Handled, Request, Response},
+    state::State,
+};
+
+// Every probe reports healthy; the anti-virus is reported disabled as on a default install.
+pub(crate) fn route(_state: &mut State, request: &Request) -> Handled {
+    match (&request.method, request.segments().as_slice()) {
+        (&Method::GET, ["health"])
+        | (&Method::GET, ["health", "db"])
+        | (&Method::GET, ["health", "cache"])
+        | (&Method::GET, ["health", "webhooks"])
+        | (&Method::GET, ["health", "logs"])
+        | (&Method::GET, ["health", "certificates"])
+        | (&Method::GET, ["health", "functions"])
+        | (&Method::GET, ["health", "storage", "local"]) => {
+            Response::ok(json!({ "ping": 0, "status": "pass" }))
+        }
+        (&Method::GET, ["health", "time"]) => {
+            let now = chrono::Utc::now().timestamp();
+            Response::ok(json!({ "remoteTime": now, "localTime": now, "diff": 0 }))
+        }
+        (&Method::GET, ["health", "version"]) => Response::ok(json!({ "version": "1.2.0" })),
+        (&Method::GET, ["health", "anti-virus"]) => {
+            Response::ok(json!({ "version": "", "status": "disabled" }))
+        }
+        (&Method::GET, ["health", "queue", _]) => Response::ok(json!({ "size": 0 })),
+        _ => route_not_found(),
+    }
+}
+
+#[tokio::test]
+async fn test_health() {
+    use appwrite::{client::RequestData, prelude::*};
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.client();
+    let health = HealthService::get_local_storage(&client).await.unwrap();
+    assert!(matches!(health.status, HealthStatus::Pass));
+
+    let time = HealthService::get_time(&client).await.unwrap();
+    assert_eq!(time.diff, 0);
+    assert!(time.remote_time > 0);
+
+    let version = HealthService::get_version(&client).await.unwrap();
+    assert_eq!(version.version, "1.2.0");
+
+    let antivirus = HealthService::get_antivirus(&client).await.unwrap();
+    assert!(matches!(antivirus.status, HealthAntivirusStatus::Disabled));
+
+    let response = client
+        .call(Method::GET, "/health/unknown", RequestData::None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,14 @@
+mod account;
+mod databases;
+mod health;
+mod mock;
+mod query;
+mod request;
+mod server;
+mod state;
+mod storage;
+mod teams;
+mod users;
+
+pub use mock::{Expectation, MockResponse, MockServer};
+pub use server::FakeServer;
@@ -0,0 +1,369 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use appwrite::{
+    client::{AppWriteClient, AppWriteClientBuilder},
+    error::Error,
+};
+use bytes::Bytes;
+use hyper::{header::CONTENT_TYPE, Method, StatusCode};
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+use crate::{
+    request::{Body, Request},
+    server::{serve, API_KEY, PROJECT_ID},
+};
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+}
+
+impl MockResponse {
+    pub fn new(status: u16) -> Self {
+        MockResponse {
+            status: StatusCode::from_u16(status).expect("invalid status code"),
+            headers: Vec::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    pub fn json(status: u16, body: Value) -> Self {
+        MockResponse {
+            body: Bytes::from(body.to_string()),
+            ..MockResponse::new(status)
+        }
+        .header(CONTENT_TYPE.as_str(), "application/json")
+    }
+
+    // Shapes the body like an Appwrite error so `Error::kind` works on the client side.
+    pub fn error(status: u16, kind: &str, message: &str) -> Self {
+        MockResponse::json(
+            status,
+            json!({
+                "message": message,
+                "code": status,
+                "type": kind,
+                "version": "1.2.0",
+            }),
+        )
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn to_hyper(&self) -> hyper::Response<hyper::Body> {
+        let mut builder = hyper::Response::builder().status(self.status);
+        for (name, value) in self.headers.iter() {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder.body(hyper::Body::from(self.body.clone())).unwrap()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Expectation {
+    method: Method,
+    path: Vec<String>,
+    headers: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+    body: Option<Value>,
+    times: Option<usize>,
+    responses: Vec<MockResponse>,
+    calls: usize,
+}
+
+impl Expectation {
+    pub fn new(method: Method, path: &str) -> Self {
+        Expectation {
+            method,
+            path: split_path(path),
+            headers: Vec::new(),
+            query: Vec::new(),
+            body: None,
+            times: None,
+            responses: Vec::new(),
+            calls: 0,
+        }
+    }
+
+    pub fn get(path: &str) -> Self {
+        Expectation::new(Method::GET, path)
+    }
+
+    pub fn post(path: &str) -> Self {
+        Expectation::new(Method::POST, path)
+    }
+
+    pub fn put(path: &str) -> Self {
+        Expectation::new(Method::PUT, path)
+    }
+
+    pub fn patch(path: &str) -> Self {
+        Expectation::new(Method::PATCH, path)
+    }
+
+    pub fn delete(path: &str) -> Self {
+        Expectation::new(Method::DELETE, path)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn json_body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    // Without `times` the expectation must be hit at least once and keeps answering afterwards.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    // Responses are played in order; the last one repeats once the sequence runs out.
+    pub fn respond(mut self, response: MockResponse) -> Self {
+        self.responses.push(response);
+        self
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        request.method == self.method
+            && request.path == self.path
+            && self
+                .headers
+                .iter()
+                .all(|(name, value)| request.header(name) == Some(value.as_str()))
+            && self
+                .query
+                .iter()
+                .all(|(key, value)| request.params(key).contains(&value.as_str()))
+            && self.body.as_ref().is_none_or(|body| match request.body {
+                Body::Json(ref value) => value == body,
+                _ => false,
+            })
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.times.is_some_and(|times| self.calls >= times)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        match self.times {
+            Some(times) => self.calls == times,
+            None => self.calls > 0,
+        }
+    }
+
+    fn next_response(&mut self) -> hyper::Response<hyper::Body> {
+        let response = match self.responses.get(self.calls) {
+            Some(response) => response.to_hyper(),
+            None => match self.responses.last() {
+                Some(response) => response.to_hyper(),
+                None => MockResponse::new(200).to_hyper(),
+            },
+        };
+        self.calls += 1;
+        response
+    }
+
+    fn describe(&self) -> String {
+        let mut description = format!("{} /{}", self.method, self.path.join("/"));
+        for (key, value) in self.query.iter() {
+            description.push_str(&format!(" {}={}", key, value));
+        }
+        match self.times {
+            Some(times) => description.push_str(&format!(" (expected {} calls", times)),
+            None => description.push_str(" (expected at least one call"),
+        }
+        description.push_str(&format!(", received {})", self.calls));
+        description
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    expectations: Vec<Expectation>,
+    unexpected: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    pub async fn start() -> Result<Self, Error> {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let shared = state.clone();
+        let (addr, shutdown) =
+            serve(move |request| {
+                let mut state = shared.lock().unwrap();
+                match state.expectations.iter_mut().find(|expectation| {
+                    !expectation.is_exhausted() && expectation.matches(&request)
+                }) {
+                    Some(expectation) => expectation.next_response(),
+                    None => {
+                        let description = describe_request(&request);
+                        state.unexpected.push(description.clone());
+                        MockResponse::error(
+                            500,
+                            "general_mock",
+                            &format!("No expectation matches {}", description),
+                        )
+                        .to_hyper()
+                    }
+                }
+            })?;
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    pub fn project_id(&self) -> &str {
+        PROJECT_ID
+    }
+
+    pub fn builder(&self) -> AppWriteClientBuilder {
+        AppWriteClient::builder(&self.url(), PROJECT_ID)
+            .set_key(API_KEY)
+            .unwrap()
+    }
+
+    pub fn client(&self) -> AppWriteClient {
+        self.builder().build().unwrap()
+    }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.state.lock().unwrap().expectations.push(expectation);
+    }
+
+    pub fn verify(&self) {
+        let state = self.state.lock().unwrap();
+        let mut problems = state
+            .expectations
+            .iter()
+            .filter(|expectation| !expectation.is_satisfied())
+            .map(|expectation| format!("unmet expectation: {}", expectation.describe()))
+            .collect::<Vec<_>>();
+        problems.extend(
+            state
+                .unexpected
+                .iter()
+                .map(|request| format!("unexpected request: {}", request)),
+        );
+        if !problems.is_empty() {
+            panic!("mock server verification failed:\n{}", problems.join("\n"));
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = MockState::default();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        if !std::thread::panicking() {
+            self.verify();
+        }
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.trim_start_matches('/')
+        .trim_start_matches("v1")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn describe_request(request: &Request) -> String {
+    let mut description = format!("{} /{}", request.method, request.path.join("/"));
+    for (key, value) in request.query.iter() {
+        description.push_str(&format!(" {}={}", key, value));
+    }
+    description
+}
+
+#[tokio::test]
+async fn test_expectations() {
+    use appwrite::prelude::*;
+
+    let mock = MockServer::start().await.unwrap();
+    mock.expect(
+        Expectation::get("/databases/db/collections/c/documents")
+            .query("queries[]", "limit(10)")
+            .header("x-appwrite-project", PROJECT_ID)
+            .times(1)
+            .respond(MockResponse::json(
+                200,
+                json!({ "total": 0, "documents": [] }),
+            )),
+    );
+    mock.expect(
+        Expectation::put("/databases/db")
+            .json_body(json!({ "name": "Renamed" }))
+            .times(2)
+            .respond(MockResponse::new(503))
+            .respond(MockResponse::error(
+                404,
+                "database_not_found",
+                "Database not found",
+            )),
+    );
+    let client = mock
+        .builder()
+        .retry_policy(RetryPolicy::new(2).base_delay(std::time::Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    let database_id = DatabaseId::new("db".to_string());
+
+    let documents = DatabasesService::list_documents::<Value>(
+        &client,
+        &database_id,
+        &CollectionId::new("c".to_string()),
+        Some(vec![Query::Limit(10)]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(documents.total, 0);
+
+    let error = DatabasesService::update_database_name(&client, &database_id, "Renamed")
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::DatabaseNotFound));
+}
+
+#[tokio::test]
+#[should_panic(expected = "unmet expectation: DELETE /databases/db")]
+async fn test_unmet_expectation() {
+    let mock = MockServer::start().await.unwrap();
+    mock.expect(Expectation::delete("/databases/db").respond(MockResponse::new(204)));
+}
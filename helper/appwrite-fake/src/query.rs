@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+
+use serde_json::{json, Value};
+
+use crate::request::{Request, Response};
+
+const DEFAULT_LIMIT: usize = 25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Query {
+    Equal(String, Vec<Value>),
+    NotEqual(String, Vec<Value>),
+    LessThan(String, Value),
+    LessThanEqual(String, Value),
+    GreaterThan(String, Value),
+    GreaterThanEqual(String, Value),
+    Search(String, String),
+    OrderAsc(String),
+    OrderDesc(String),
+    Limit(usize),
+    Offset(usize),
+    CursorAfter(String),
+    CursorBefore(String),
+}
+
+// Queries look like `equal("title",["a","b"])`; the argument list is valid JSON once wrapped in brackets.
+pub(crate) fn parse(query: &str) -> Result<Query, Response> {
+    let invalid = || {
+        Response::invalid(
+            "general_query_invalid",
+            &format!("Invalid query: {}", query),
+        )
+    };
+    let open = query.find('(').ok_or_else(invalid)?;
+    if !query.ends_with(')') {
+        return Err(invalid());
+    }
+    let method = &query[..open];
+    let args: Vec<Value> =
+        serde_json::from_str(&format!("[{}]", &query[open + 1..query.len() - 1]))
+            .map_err(|_| invalid())?;
+    let attribute = || {
+        args.first()
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(invalid)
+    };
+    let values = || match args.get(1) {
+        Some(Value::Array(values)) => Ok(values.clone()),
+        Some(value) => Ok(vec![value.clone()]),
+        None => Err(invalid()),
+    };
+    let single = || values()?.into_iter().next().ok_or_else(invalid);
+    let number = || {
+        args.first()
+            .and_then(Value::as_u64)
+            .map(|n| n as usize)
+            .ok_or_else(invalid)
+    };
+    Ok(match method {
+        "equal" => Query::Equal(attribute()?, values()?),
+        "notEqual" => Query::NotEqual(attribute()?, values()?),
+        "lessThan" => Query::LessThan(attribute()?, single()?),
+        "lessThanEqual" => Query::LessThanEqual(attribute()?, single()?),
+        "greaterThan" => Query::GreaterThan(attribute()?, single()?),
+        "greaterThanEqual" => Query::GreaterThanEqual(attribute()?, single()?),
+        "search" => Query::Search(
+            attribute()?,
+            single()?.as_str().map(str::to_string).ok_or_else(invalid)?,
+        ),
+        "orderAsc" => Query::OrderAsc(attribute()?),
+        "orderDesc" => Query::OrderDesc(attribute()?),
+        "limit" => Query::Limit(number()?),
+        "offset" => Query::Offset(number()?),
+        "cursorAfter" => Query::CursorAfter(attribute()?),
+        "cursorBefore" => Query::CursorBefore(attribute()?),
+        _ => return Err(invalid()),
+    })
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Null, _) => Some(Ordering::Less),
+        (_, Value::Null) => Some(Ordering::Greater),
+        _ => None,
+    }
+}
+
+fn matches_any(field: &Value, values: &[Value]) -> bool {
+    let candidates = match field {
+        Value::Array(items) => items.clone(),
+        value => vec![value.clone()],
+    };
+    candidates.iter().any(|candidate| {
+        values
+            .iter()
+            .any(|value| compare(candidate, value) == Some(Ordering::Equal))
+    })
+}
+
+fn matches(item: &Value, query: &Query) -> bool {
+    let check = |key: &str, f: &dyn Fn(Ordering) -> bool, value: &Value| {
+        compare(&item[key], value).is_some_and(f)
+    };
+    match query {
+        Query::Equal(key, values) => matches_any(&item[key.as_str()], values),
+        Query::NotEqual(key, values) => !matches_any(&item[key.as_str()], values),
+        Query::LessThan(key, value) => check(key, &|o| o == Ordering::Less, value),
+        Query::LessThanEqual(key, value) => check(key, &|o| o != Ordering::Greater, value),
+        Query::GreaterThan(key, value) => check(key, &|o| o == Ordering::Greater, value),
+        Query::GreaterThanEqual(key, value) => check(key, &|o| o != Ordering::Less, value),
+        Query::Search(key, term) => {
+            let text = item[key.as_str()]
+                .as_str()
+                .unwrap_or_default()
+                .to_lowercase();
+            term.to_lowercase()
+                .split_whitespace()
+                .any(|word| text.contains(word))
+        }
+        _ => true,
+    }
+}
+
+pub(crate) fn apply(items: Vec<Value>, queries: &[Query]) -> Result<(usize, Vec<Value>), Response> {
+    let mut items = items
+        .into_iter()
+        .filter(|item| queries.iter().all(|query| matches(item, query)))
+        .collect::<Vec<_>>();
+    for query in queries.iter().rev() {
+        match query {
+            Query::OrderAsc(key) => items.sort_by(|a, b| {
+                compare(&a[key.as_str()], &b[key.as_str()]).unwrap_or(Ordering::Equal)
+            }),
+            Query::OrderDesc(key) => items.sort_by(|a, b| {
+                compare(&b[key.as_str()], &a[key.as_str()]).unwrap_or(Ordering::Equal)
+            }),
+            _ => {}
+        }
+    }
+    let total = items.len();
+    let mut start = 0;
+    let mut end = items.len();
+    for query in queries {
+        let cursor_not_found = || Response::invalid("general_cursor_not_found", "Cursor not found");
+        match query {
+            Query::CursorAfter(id) => {
+                start = items
+                    .iter()
+                    .position(|item| item["$id"].as_str() == Some(id.as_str()))
+                    .ok_or_else(cursor_not_found)?
+                    + 1;
+            }
+            Query::CursorBefore(id) => {
+                end = items
+                    .iter()
+                    .position(|item| item["$id"].as_str() == Some(id.as_str()))
+                    .ok_or_else(cursor_not_found)?;
+            }
+            _ => {}
+        }
+    }
+    let offset = queries
+        .iter()
+        .find_map(|query| match query {
+            Query::Offset(offset) => Some(*offset),
+            _ => None,
+        })
+        .unwrap_or(0);
+    let limit = queries
+        .iter()
+        .find_map(|query| match query {
+            Query::Limit(limit) => Some(*limit),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_LIMIT);
+    let page = if queries
+        .iter()
+        .any(|query| matches!(query, Query::CursorBefore(_)))
+    {
+        // A backwards cursor returns the page that ends right before the cursor.
+        let slice = &items[start.min(end)..end];
+        let skip = slice.len().saturating_sub(offset + limit);
+        slice[skip..slice.len().saturating_sub(offset)].to_vec()
+    } else {
+        items[start.min(end)..end]
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    };
+    Ok((total, page))
+}
+
+// List endpoints accept both `queries[]` and the older `limit`/`offset`/`search` parameters.
+pub(crate) fn list(
+    key: &str,
+    items: Vec<Value>,
+    request: &Request,
+    search_fields: &[&str],
+) -> Result<Response, Response> {
+    let mut queries = request
+        .params("queries[]")
+        .into_iter()
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(limit) = request.param("limit").and_then(|v| v.parse().ok()) {
+        queries.push(Query::Limit(limit));
+    }
+    if let Some(offset) = request.param("offset").and_then(|v| v.parse().ok()) {
+        queries.push(Query::Offset(offset));
+    }
+    let search = request
+        .param("search")
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase);
+    let items = match search {
+        Some(term) => items
+            .into_iter()
+            .filter(|item| {
+                search_fields.iter().any(|field| {
+                    item[*field]
+                        .as_str()
+                        .is_some_and(|value| value.to_lowercase().contains(&term))
+                })
+            })
+            .collect(),
+        None => items,
+    };
+    let (total, page) = apply(items, &queries)?;
+    Response::ok(json!({ key: page, "total": total }))
+}
+
+#[test]
+fn test_parse_and_apply() {
+    let docs = vec![
+        json!({ "$id": "a", "title": "Hello world", "count": 3 }),
+        json!({ "$id": "b", "title": "Goodbye", "count": 1 }),
+        json!({ "$id": "c", "title": "Hello again", "count": 2 }),
+    ];
+    let queries = [
+        parse(r#"search("title",["hello"])"#).unwrap(),
+        parse(r#"orderAsc("count")"#).unwrap(),
+    ];
+    let (total, page) = apply(docs.clone(), &queries).unwrap();
+    assert_eq!(total, 2);
+    assert_eq!(page[0]["$id"], "c");
+
+    let queries = [
+        parse(r#"greaterThanEqual("count",[2])"#).unwrap(),
+        parse("limit(1)").unwrap(),
+        parse(r#"cursorAfter("a")"#).unwrap(),
+    ];
+    let (total, page) = apply(docs, &queries).unwrap();
+    assert_eq!(total, 2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["$id"], "c");
+    assert!(parse("bogus").is_err());
+}
@@ -0,0 +1,241 @@
+use bytes::Bytes;
+use hyper::{
+    header::{HeaderValue, CONTENT_TYPE},
+    HeaderMap, Method, StatusCode,
+};
+use rand::Rng;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone)]
+pub(crate) struct UploadedFile {
+    pub name: String,
+    pub mime_type: String,
+    pub data: Bytes,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Body {
+    Empty,
+    Json(Value),
+    Multipart {
+        fields: Vec<(String, String)>,
+        file: Option<UploadedFile>,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct Request {
+    pub method: Method,
+    pub path: Vec<String>,
+    pub query: Vec<(String, String)>,
+    pub headers: HeaderMap<HeaderValue>,
+    pub body: Body,
+}
+
+impl Request {
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.iter().map(String::as_str).collect()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn params(&self, key: &str) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    pub fn json(&self, key: &str) -> Option<&Value> {
+        match self.body {
+            Body::Json(ref value) => value.get(key).filter(|value| !value.is_null()),
+            _ => None,
+        }
+    }
+
+    // Multipart and JSON bodies are read the same way so upload fields look like any other argument.
+    pub fn string(&self, key: &str) -> Option<String> {
+        match self.body {
+            Body::Json(_) => self.json(key).and_then(|value| match value {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                Value::Bool(b) => Some(b.to_string()),
+                _ => None,
+            }),
+            Body::Multipart { ref fields, .. } => fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone()),
+            Body::Empty => None,
+        }
+    }
+
+    pub fn require(&self, key: &str) -> Result<String, Response> {
+        self.string(key).ok_or_else(|| {
+            Response::error(
+                StatusCode::BAD_REQUEST,
+                "general_argument_invalid",
+                &format!("Param \"{}\" is not optional.", key),
+            )
+        })
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        self.json(key).and_then(Value::as_bool)
+    }
+
+    pub fn strings(&self, key: &str) -> Option<Vec<String>> {
+        match self.body {
+            Body::Json(_) => self.json(key).and_then(Value::as_array).map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            }),
+            Body::Multipart { ref fields, .. } => {
+                let key = format!("{}[]", key);
+                let values = fields
+                    .iter()
+                    .filter(|(k, _)| *k == key)
+                    .map(|(_, v)| v.clone())
+                    .collect::<Vec<_>>();
+                Some(values)
+            }
+            Body::Empty => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Response {
+    pub status: StatusCode,
+    pub content_type: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Bytes,
+}
+
+pub(crate) type Handled = Result<Response, Response>;
+
+impl Response {
+    pub fn json(status: StatusCode, value: Value) -> Self {
+        Response {
+            status,
+            content_type: "application/json".to_string(),
+            headers: Vec::new(),
+            body: Bytes::from(value.to_string()),
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: String) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    pub fn ok(value: Value) -> Handled {
+        Ok(Response::json(StatusCode::OK, value))
+    }
+
+    pub fn created(value: Value) -> Handled {
+        Ok(Response::json(StatusCode::CREATED, value))
+    }
+
+    pub fn accepted(value: Value) -> Handled {
+        Ok(Response::json(StatusCode::ACCEPTED, value))
+    }
+
+    pub fn no_content() -> Handled {
+        Ok(Response {
+            status: StatusCode::NO_CONTENT,
+            content_type: "text/plain".to_string(),
+            headers: Vec::new(),
+            body: Bytes::new(),
+        })
+    }
+
+    pub fn bytes(mime_type: &str, data: Bytes) -> Handled {
+        Ok(Response {
+            status: StatusCode::OK,
+            content_type: mime_type.to_string(),
+            headers: Vec::new(),
+            body: data,
+        })
+    }
+
+    pub fn error(status: StatusCode, kind: &str, message: &str) -> Self {
+        Response::json(
+            status,
+            json!({
+                "message": message,
+                "code": status.as_u16(),
+                "type": kind,
+                "version": "1.2.0",
+            }),
+        )
+    }
+
+    pub fn not_found(kind: &str, message: &str) -> Self {
+        Response::error(StatusCode::NOT_FOUND, kind, message)
+    }
+
+    pub fn conflict(kind: &str, message: &str) -> Self {
+        Response::error(StatusCode::CONFLICT, kind, message)
+    }
+
+    pub fn invalid(kind: &str, message: &str) -> Self {
+        Response::error(StatusCode::BAD_REQUEST, kind, message)
+    }
+
+    pub fn into_hyper(self) -> hyper::Response<hyper::Body> {
+        let mut builder = hyper::Response::builder()
+            .status(self.status)
+            .header(CONTENT_TYPE, self.content_type);
+        for (name, value) in self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(hyper::Body::from(self.body)).unwrap()
+    }
+}
+
+pub(crate) fn route_not_found() -> Handled {
+    Err(Response::not_found(
+        "general_route_not_found",
+        "The requested route was not found.",
+    ))
+}
+
+pub(crate) fn now() -> String {
+    chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3f+00:00")
+        .to_string()
+}
+
+pub(crate) fn later(days: i64) -> String {
+    (chrono::Utc::now() + chrono::Duration::days(days))
+        .format("%Y-%m-%dT%H:%M:%S%.3f+00:00")
+        .to_string()
+}
+
+pub(crate) fn resolve_id(id: &str) -> String {
+    if id == "unique()" {
+        unique_id()
+    } else {
+        id.to_string()
+    }
+}
+
+pub(crate) fn unique_id() -> String {
+    let mut rng = rand::thread_rng();
+    (0..20)
+        .map(|_| std::char::from_digit(rng.gen_range(0..16), 16).unwrap())
+        .collect()
+}
@@ -0,0 +1,233 @@
+use std::{
+    convert::Infallible,
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, Mutex},
+};
+
+use appwrite::{
+    client::{AppWriteClient, AppWriteClientBuilder},
+    error::Error,
+};
+use bytes::Bytes;
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body as HyperBody, Server,
+};
+use serde_json::Value;
+use tokio::sync::oneshot;
+
+use crate::{
+    account, databases, health,
+    request::{route_not_found, unique_id, Body, Handled, Request, Response, UploadedFile},
+    state::State,
+    storage, teams, users,
+};
+
+pub const PROJECT_ID: &str = "fake-project";
+pub const API_KEY: &str = "fake-api-key";
+
+#[derive(Debug)]
+pub struct FakeServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl FakeServer {
+    pub async fn start() -> Result<Self, Error> {
+        let state = Arc::new(Mutex::new(State::new(PROJECT_ID)));
+        let shared = state.clone();
+        let (addr, shutdown) = serve(move |request| {
+            let mut state = shared.lock().unwrap();
+            match route(&mut state, &request) {
+                Ok(response) | Err(response) => response.into_hyper(),
+            }
+        })?;
+        Ok(FakeServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    pub fn project_id(&self) -> &str {
+        PROJECT_ID
+    }
+
+    pub fn builder(&self) -> AppWriteClientBuilder {
+        AppWriteClient::builder(&self.url(), PROJECT_ID)
+            .set_key(API_KEY)
+            .unwrap()
+    }
+
+    pub fn client(&self) -> AppWriteClient {
+        self.builder().build().unwrap()
+    }
+
+    // Returns a JWT the fake accepts for `/account` routes, to be used with `AppWriteClient::with_jwt`.
+    pub fn create_jwt(&self, user_id: &str) -> String {
+        let jwt = format!("fake-jwt.{}", unique_id());
+        self.state
+            .lock()
+            .unwrap()
+            .jwts
+            .insert(jwt.clone(), user_id.to_string());
+        jwt
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = State::new(PROJECT_ID);
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+// Binds an ephemeral local port and serves `handler` on the current runtime until `shutdown` fires.
+pub(crate) fn serve<F>(handler: F) -> Result<(SocketAddr, oneshot::Sender<()>), Error>
+where
+    F: Fn(Request) -> hyper::Response<HyperBody> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.set_nonblocking(true)?;
+    let addr = listener.local_addr()?;
+    let (shutdown, signal) = oneshot::channel::<()>();
+    let handler = Arc::new(handler);
+    let make_service = make_service_fn(move |_| {
+        let handler = handler.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let handler = handler.clone();
+                async move {
+                    let response = match parse(request).await {
+                        Ok(request) => handler(request),
+                        Err(response) => response.into_hyper(),
+                    };
+                    Ok::<_, Infallible>(response)
+                }
+            }))
+        }
+    });
+    let server = Server::from_tcp(listener)
+        .map_err(|error| Error::Transport(error.to_string().into()))?
+        .serve(make_service)
+        .with_graceful_shutdown(async {
+            signal.await.ok();
+        });
+    tokio::spawn(server);
+    Ok((addr, shutdown))
+}
+
+async fn parse(request: hyper::Request<HyperBody>) -> Result<Request, Response> {
+    let (parts, body) = request.into_parts();
+    let invalid_body = |error: &dyn std::fmt::Display| {
+        Response::invalid(
+            "general_argument_invalid",
+            &format!("Invalid body: {}", error),
+        )
+    };
+    let body = hyper::body::to_bytes(body)
+        .await
+        .map_err(|error| invalid_body(&error))?;
+    let content_type = parts
+        .headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = if body.is_empty() {
+        Body::Empty
+    } else if content_type.starts_with("application/json") {
+        Body::Json(serde_json::from_slice(&body).map_err(|error| invalid_body(&error))?)
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        let map = url::form_urlencoded::parse(&body)
+            .map(|(key, value)| (key.into_owned(), Value::String(value.into_owned())))
+            .collect();
+        Body::Json(Value::Object(map))
+    } else if content_type.starts_with("multipart/form-data") {
+        parse_multipart(&content_type, body)
+            .await
+            .map_err(|error| invalid_body(&error))?
+    } else {
+        Body::Empty
+    };
+    let path = parts
+        .uri
+        .path()
+        .trim_start_matches('/')
+        .trim_start_matches("v1")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect();
+    let query = parts
+        .uri
+        .query()
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Request {
+        method: parts.method,
+        path,
+        query,
+        headers: parts.headers,
+        body,
+    })
+}
+
+async fn parse_multipart(content_type: &str, body: Bytes) -> Result<Body, multer::Error> {
+    let boundary = multer::parse_boundary(content_type)?;
+    let stream = futures_util::stream::once(async move { Ok::<_, Infallible>(body) });
+    let mut multipart = multer::Multipart::new(stream, boundary);
+    let mut fields = Vec::new();
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await? {
+        let name = field.name().unwrap_or_default().to_string();
+        match field.file_name().map(str::to_string) {
+            Some(file_name) => {
+                let mime_type = field
+                    .content_type()
+                    .map(|mime| mime.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                file = Some(UploadedFile {
+                    name: file_name,
+                    mime_type,
+                    data: field.bytes().await?,
+                });
+            }
+            None => fields.push((name, field.text().await?)),
+        }
+    }
+    Ok(Body::Multipart { fields, file })
+}
+
+fn route(state: &mut State, request: &Request) -> Handled {
+    if request.header("x-appwrite-project") != Some(state.project_id.as_str()) {
+        return Err(Response::not_found(
+            "project_not_found",
+            "Project with the requested ID could not be found.",
+        ));
+    }
+    match request.path.first().map(String::as_str) {
+        Some("account") => account::route(state, request),
+        Some("databases") => databases::route(state, request),
+        Some("health") => health::route(state, request),
+        Some("storage") => storage::route(state, request),
+        Some("teams") => teams::route(state, request),
+        Some("users") => users::route(state, request),
+        _ => route_not_found(),
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+// Records are kept in the JSON shape Appwrite returns, so handlers can hand them out as-is.
+#[derive(Debug, Default)]
+pub(crate) struct State {
+    pub project_id: String,
+    pub databases: Vec<Value>,
+    pub collections: Vec<Value>,
+    pub documents: Vec<Value>,
+    pub buckets: Vec<Value>,
+    pub files: Vec<Value>,
+    pub file_data: HashMap<String, Vec<u8>>,
+    pub users: Vec<Value>,
+    pub passwords: HashMap<String, String>,
+    pub user_prefs: HashMap<String, Value>,
+    pub teams: Vec<Value>,
+    pub memberships: Vec<Value>,
+    pub sessions: Vec<Value>,
+    pub tokens: Vec<Value>,
+    pub jwts: HashMap<String, String>,
+    // Session cookie secret -> session ID.
+    pub session_secrets: HashMap<String, String>,
+}
+
+impl State {
+    pub fn new(project_id: &str) -> Self {
+        State {
+            project_id: project_id.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+pub(crate) fn has_id(item: &Value, id: &str) -> bool {
+    item["$id"].as_str() == Some(id)
+}
+
+pub(crate) fn field_is(item: &Value, key: &str, value: &str) -> bool {
+    item[key].as_str() == Some(value)
+}
+
+pub(crate) fn find<'a>(items: &'a [Value], id: &str) -> Option<&'a Value> {
+    items.iter().find(|item| has_id(item, id))
+}
+
+pub(crate) fn find_mut<'a>(items: &'a mut [Value], id: &str) -> Option<&'a mut Value> {
+    items.iter_mut().find(|item| has_id(item, id))
+}
@@ -0,0 +1,315 @@
+use bytes::Bytes;
+use hyper::Method;
+use serde_json::{json, Value};
+
+use crate::{
+    query,
+    request::{now, resolve_id, route_not_found, Body, Handled, Request, Response},
+    state::{field_is, find, find_mut, has_id, State},
+};
+
+const MAXIMUM_FILE_SIZE: u64 = 30_000_000;
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Handled {
+    match (&request.method, request.segments().as_slice()) {
+        (&Method::POST, ["storage", "buckets"]) => create_bucket(state, request),
+        (&Method::GET, ["storage", "buckets"]) => {
+            query::list("buckets", state.buckets.clone(), request, &["name"])
+        }
+        (&Method::GET, ["storage", "buckets", bucket]) => Response::ok(
+            find(&state.buckets, bucket)
+                .ok_or_else(bucket_not_found)?
+                .clone(),
+        ),
+        (&Method::PUT, ["storage", "buckets", bucket]) => update_bucket(state, request, bucket),
+        (&Method::DELETE, ["storage", "buckets", bucket]) => {
+            find(&state.buckets, bucket).ok_or_else(bucket_not_found)?;
+            state.buckets.retain(|item| !has_id(item, bucket));
+            let files = &mut state.files;
+            let data = &mut state.file_data;
+            files.retain(|item| {
+                let keep = !field_is(item, "bucketId", bucket);
+                if !keep {
+                    data.remove(&data_key(bucket, item["$id"].as_str().unwrap_or_default()));
+                }
+                keep
+            });
+            Response::no_content()
+        }
+        (&Method::POST, ["storage", "buckets", bucket, "files"]) => {
+            create_file(state, request, bucket)
+        }
+        (&Method::GET, ["storage", "buckets", bucket, "files"]) => {
+            find(&state.buckets, bucket).ok_or_else(bucket_not_found)?;
+            let items = state
+                .files
+                .iter()
+                .filter(|item| field_is(item, "bucketId", bucket))
+                .cloned()
+                .collect();
+            query::list("files", items, request, &["name"])
+        }
+        (&Method::GET, ["storage", "buckets", bucket, "files", file]) => {
+            Response::ok(file_record(state, bucket, file)?.clone())
+        }
+        (&Method::PUT, ["storage", "buckets", bucket, "files", file]) => {
+            let permissions = request.strings("permissions");
+            let record = file_record_mut(state, bucket, file)?;
+            if let Some(permissions) = permissions {
+                record["$permissions"] = json!(permissions);
+            }
+            record["$updatedAt"] = json!(now());
+            Response::ok(record.clone())
+        }
+        (&Method::DELETE, ["storage", "buckets", bucket, "files", file]) => {
+            file_record(state, bucket, file)?;
+            state
+                .files
+                .retain(|item| !(has_id(item, file) && field_is(item, "bucketId", bucket)));
+            state.file_data.remove(&data_key(bucket, file));
+            Response::no_content()
+        }
+        (
+            &Method::GET,
+            ["storage", "buckets", bucket, "files", file, "download" | "view" | "preview"],
+        ) => {
+            let mime_type = file_record(state, bucket, file)?["mimeType"]
+                .as_str()
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            let data = state
+                .file_data
+                .get(&data_key(bucket, file))
+                .cloned()
+                .unwrap_or_default();
+            Response::bytes(&mime_type, Bytes::from(data))
+        }
+        _ => route_not_found(),
+    }
+}
+
+fn data_key(bucket: &str, file: &str) -> String {
+    format!("{}/{}", bucket, file)
+}
+
+fn bucket_not_found() -> Response {
+    Response::not_found(
+        "storage_bucket_not_found",
+        "Storage bucket with the requested ID could not be found.",
+    )
+}
+
+fn file_not_found() -> Response {
+    Response::not_found(
+        "storage_file_not_found",
+        "The requested file could not be found.",
+    )
+}
+
+fn file_record<'a>(state: &'a State, bucket: &str, file: &str) -> Result<&'a Value, Response> {
+    find(&state.buckets, bucket).ok_or_else(bucket_not_found)?;
+    state
+        .files
+        .iter()
+        .find(|item| has_id(item, file) && field_is(item, "bucketId", bucket))
+        .ok_or_else(file_not_found)
+}
+
+fn file_record_mut<'a>(
+    state: &'a mut State,
+    bucket: &str,
+    file: &str,
+) -> Result<&'a mut Value, Response> {
+    find(&state.buckets, bucket).ok_or_else(bucket_not_found)?;
+    state
+        .files
+        .iter_mut()
+        .find(|item| has_id(item, file) && field_is(item, "bucketId", bucket))
+        .ok_or_else(file_not_found)
+}
+
+fn create_bucket(state: &mut State, request: &Request) -> Handled {
+    let id = resolve_id(&request.require("bucketId")?);
+    if find(&state.buckets, &id).is_some() {
+        return Err(Response::conflict(
+            "storage_bucket_already_exists",
+            "A storage bucket with the requested ID already exists.",
+        ));
+    }
+    let now = now();
+    let bucket = json!({
+        "$id": id,
+        "$createdAt": now,
+        "$updatedAt": now,
+        "$permissions": request.strings("permissions").unwrap_or_default(),
+        "fileSecurity": request.bool("fileSecurity").unwrap_or(true),
+        "name": request.require("name")?,
+        "enabled": request.bool("enabled").unwrap_or(true),
+        "maximumFileSize": request
+            .json("maximumFileSize")
+            .and_then(Value::as_u64)
+            .unwrap_or(MAXIMUM_FILE_SIZE),
+        "allowedFileExtensions": request.strings("allowedFileExtensions").unwrap_or_default(),
+        "compression": request.string("compression").unwrap_or_else(|| "none".to_string()),
+        "encryption": request.bool("encryption").unwrap_or(true),
+        "antivirus": request.bool("antivirus").unwrap_or(true),
+    });
+    state.buckets.push(bucket.clone());
+    Response::created(bucket)
+}
+
+fn update_bucket(state: &mut State, request: &Request, bucket: &str) -> Handled {
+    let name = request.require("name")?;
+    let record = find_mut(&mut state.buckets, bucket).ok_or_else(bucket_not_found)?;
+    record["name"] = json!(name);
+    if let Some(permissions) = request.strings("permissions").filter(|p| !p.is_empty()) {
+        record["$permissions"] = json!(permissions);
+    }
+    for key in ["enabled", "fileSecurity", "encryption", "antivirus"] {
+        if let Some(value) = request.bool(key) {
+            record[key] = json!(value);
+        }
+    }
+    if let Some(size) = request.json("maximumFileSize").and_then(Value::as_u64) {
+        record["maximumFileSize"] = json!(size);
+    }
+    if let Some(extensions) = request
+        .strings("allowedFileExtensions")
+        .filter(|e| !e.is_empty())
+    {
+        record["allowedFileExtensions"] = json!(extensions);
+    }
+    if let Some(compression) = request.string("compression") {
+        record["compression"] = json!(compression);
+    }
+    record["$updatedAt"] = json!(now());
+    Response::ok(record.clone())
+}
+
+// Parses `bytes start-end/total`. Only the start and total are trusted; the chunk length comes from the body.
+fn content_range(request: &Request) -> Result<Option<(usize, usize)>, Response> {
+    let header = match request.header("content-range") {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+    let invalid = || Response::invalid("storage_invalid_content_range", "Invalid content range.");
+    let range = header.strip_prefix("bytes ").ok_or_else(invalid)?;
+    let (range, total) = range.split_once('/').ok_or_else(invalid)?;
+    let (start, _) = range.split_once('-').ok_or_else(invalid)?;
+    let start = start.trim().parse().map_err(|_| invalid())?;
+    let total = total.trim().parse().map_err(|_| invalid())?;
+    Ok(Some((start, total)))
+}
+
+fn create_file(state: &mut State, request: &Request, bucket: &str) -> Handled {
+    let bucket_record = find(&state.buckets, bucket)
+        .ok_or_else(bucket_not_found)?
+        .clone();
+    let upload = match request.body {
+        Body::Multipart {
+            file: Some(ref file),
+            ..
+        } => file.clone(),
+        _ => {
+            return Err(Response::invalid(
+                "storage_file_empty",
+                "Empty file passed to the endpoint.",
+            ))
+        }
+    };
+    let range = content_range(request)?;
+    let total = range.map(|(_, total)| total).unwrap_or(upload.data.len());
+    if total as u64
+        > bucket_record["maximumFileSize"]
+            .as_u64()
+            .unwrap_or(MAXIMUM_FILE_SIZE)
+    {
+        return Err(Response::invalid(
+            "storage_invalid_file_size",
+            "The file size is either not valid or exceeds the maximum allowed size.",
+        ));
+    }
+    let id = match request.header("x-appwrite-id") {
+        Some(id) => id.to_string(),
+        None => resolve_id(&request.require("fileId")?),
+    };
+    let key = data_key(bucket, &id);
+    let start = range.map(|(start, _)| start).unwrap_or_default();
+
+    if start == 0 {
+        if file_record(state, bucket, &id).is_ok() {
+            return Err(Response::conflict(
+                "storage_file_already_exists",
+                "A storage file with the requested ID already exists.",
+            ));
+        }
+        let chunk = upload.data.len().max(1);
+        let now = now();
+        let file = json!({
+            "$id": id,
+            "bucketId": bucket,
+            "$createdAt": now,
+            "$updatedAt": now,
+            "$permissions": request.strings("permissions").unwrap_or_default(),
+            "name": upload.name,
+            "signature": format!("{:032x}", upload.data.len()),
+            "mimeType": upload.mime_type,
+            "sizeOriginal": total,
+            "chunksTotal": total.div_ceil(chunk).max(1),
+            "chunksUploaded": 1,
+        });
+        state.files.push(file.clone());
+        state.file_data.insert(key, upload.data.to_vec());
+        return Response::created(file);
+    }
+
+    file_record(state, bucket, &id)?;
+    let data = state.file_data.entry(key).or_default();
+    if data.len() < start + upload.data.len() {
+        data.resize(start + upload.data.len(), 0);
+    }
+    data[start..start + upload.data.len()].copy_from_slice(&upload.data);
+    let file = file_record_mut(state, bucket, &id)?;
+    file["chunksUploaded"] = json!(file["chunksUploaded"].as_u64().unwrap_or_default() + 1);
+    file["$updatedAt"] = json!(now());
+    Response::created(file.clone())
+}
+
+#[tokio::test]
+async fn test_chunked_upload() {
+    use appwrite::prelude::*;
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.builder().chunk_size(1024).build().unwrap();
+    let bucket = StoragesService::create_bucket(
+        &client,
+        CreateBucketPayload {
+            bucket_id: BucketId::new("uploads".to_string()),
+            name: "Uploads".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let data = (0..2500).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let input = InputFile::from_bytes(data.clone(), Some("data.bin".to_string()), None)
+        .await
+        .unwrap();
+    let file = StoragesService::create_file(&client, &bucket.id, &FileId::unique(), input, &[])
+        .await
+        .unwrap();
+    assert_eq!((file.chunks_total, file.chunks_uploaded), (3, 3));
+
+    let downloaded = StoragesService::get_file_preview(&client, &bucket.id, &file.id)
+        .await
+        .unwrap();
+    assert_eq!(downloaded.as_ref(), data.as_slice());
+
+    StoragesService::delete_file(&client, &bucket.id, &file.id)
+        .await
+        .unwrap();
+    let error = StoragesService::get_file(&client, &bucket.id, &file.id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::StorageFileNotFound));
+}
@@ -0,0 +1,284 @@
+use hyper::Method;
+use serde_json::{json, Value};
+
+use crate::{
+    query,
+    request::{now, resolve_id, route_not_found, unique_id, Handled, Request, Response},
+    state::{field_is, find, find_mut, has_id, State},
+    users,
+};
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Handled {
+    match (&request.method, request.segments().as_slice()) {
+        (&Method::POST, ["teams"]) => {
+            let id = resolve_id(&request.require("teamId")?);
+            if find(&state.teams, &id).is_some() {
+                return Err(Response::conflict(
+                    "team_already_exists",
+                    "Team with requested ID already exists.",
+                ));
+            }
+            let now = now();
+            let team = json!({
+                "$id": id,
+                "$createdAt": now,
+                "$updatedAt": now,
+                "name": request.require("name")?,
+                "total": 0,
+            });
+            state.teams.push(team.clone());
+            Response::created(team)
+        }
+        (&Method::GET, ["teams"]) => query::list("teams", state.teams.clone(), request, &["name"]),
+        (&Method::GET, ["teams", team]) => Response::ok(get_team(state, team)?.clone()),
+        (&Method::PUT, ["teams", team]) => {
+            let name = request.require("name")?;
+            let record = find_mut(&mut state.teams, team).ok_or_else(team_not_found)?;
+            record["name"] = json!(name);
+            record["$updatedAt"] = json!(now());
+            let record = record.clone();
+            for membership in state
+                .memberships
+                .iter_mut()
+                .filter(|item| field_is(item, "teamId", team))
+            {
+                membership["teamName"] = json!(name);
+            }
+            Response::ok(record)
+        }
+        (&Method::DELETE, ["teams", team]) => {
+            get_team(state, team)?;
+            state.teams.retain(|item| !has_id(item, team));
+            state
+                .memberships
+                .retain(|item| !field_is(item, "teamId", team));
+            Response::no_content()
+        }
+        (&Method::POST, ["teams", team, "memberships"]) => create_membership(state, request, team),
+        (&Method::GET, ["teams", team, "memberships"]) => {
+            get_team(state, team)?;
+            let items = state
+                .memberships
+                .iter()
+                .filter(|item| field_is(item, "teamId", team))
+                .cloned()
+                .collect();
+            query::list("memberships", items, request, &["userName", "userEmail"])
+        }
+        (&Method::GET, ["teams", team, "memberships", membership]) => {
+            Response::ok(get_membership(state, team, membership)?.clone())
+        }
+        (&Method::PATCH, ["teams", team, "memberships", membership]) => {
+            let roles = request.strings("roles").unwrap_or_default();
+            let record = membership_mut(state, team, membership)?;
+            record["roles"] = json!(roles);
+            record["$updatedAt"] = json!(now());
+            Response::ok(record.clone())
+        }
+        (&Method::PATCH, ["teams", team, "memberships", membership, "status"]) => {
+            let user = request.require("userId")?;
+            request.require("secret")?;
+            let record = membership_mut(state, team, membership)?;
+            if !field_is(record, "userId", &user) {
+                return Err(Response::error(
+                    hyper::StatusCode::UNAUTHORIZED,
+                    "team_invite_mismatch",
+                    "Invite does not belong to current user.",
+                ));
+            }
+            let now = now();
+            record["confirm"] = json!(true);
+            record["joined"] = json!(now);
+            record["$updatedAt"] = json!(now);
+            Response::ok(record.clone())
+        }
+        (&Method::DELETE, ["teams", team, "memberships", membership]) => {
+            get_membership(state, team, membership)?;
+            state
+                .memberships
+                .retain(|item| !(has_id(item, membership) && field_is(item, "teamId", team)));
+            let record = find_mut(&mut state.teams, team).unwrap();
+            record["total"] = json!(record["total"].as_u64().unwrap_or(1).saturating_sub(1));
+            Response::no_content()
+        }
+        _ => route_not_found(),
+    }
+}
+
+fn team_not_found() -> Response {
+    Response::not_found(
+        "team_not_found",
+        "Team with the requested ID could not be found.",
+    )
+}
+
+fn membership_not_found() -> Response {
+    Response::not_found(
+        "membership_not_found",
+        "Membership with the requested ID could not be found.",
+    )
+}
+
+fn get_team<'a>(state: &'a State, team: &str) -> Result<&'a Value, Response> {
+    find(&state.teams, team).ok_or_else(team_not_found)
+}
+
+fn get_membership<'a>(
+    state: &'a State,
+    team: &str,
+    membership: &str,
+) -> Result<&'a Value, Response> {
+    get_team(state, team)?;
+    state
+        .memberships
+        .iter()
+        .find(|item| has_id(item, membership) && field_is(item, "teamId", team))
+        .ok_or_else(membership_not_found)
+}
+
+fn membership_mut<'a>(
+    state: &'a mut State,
+    team: &str,
+    membership: &str,
+) -> Result<&'a mut Value, Response> {
+    get_team(state, team)?;
+    state
+        .memberships
+        .iter_mut()
+        .find(|item| has_id(item, membership) && field_is(item, "teamId", team))
+        .ok_or_else(membership_not_found)
+}
+
+// Invites made with an API key are confirmed immediately, creating the user if the email is unknown.
+fn create_membership(state: &mut State, request: &Request, team: &str) -> Handled {
+    let team_name = get_team(state, team)?["name"].clone();
+    let email = request.require("email")?.to_lowercase();
+    let roles = request.strings("roles").unwrap_or_default();
+    let user = match state
+        .users
+        .iter()
+        .find(|user| field_is(user, "email", &email))
+    {
+        Some(user) => user.clone(),
+        None => users::insert_user(
+            state,
+            "unique()",
+            Some(email.clone()),
+            None,
+            None,
+            request.string("name"),
+        )?,
+    };
+    let user_id = user["$id"].as_str().unwrap_or_default();
+    if state
+        .memberships
+        .iter()
+        .any(|item| field_is(item, "teamId", team) && field_is(item, "userId", user_id))
+    {
+        return Err(Response::conflict(
+            "membership_already_confirmed",
+            "User has already been invited or is already a member of this team",
+        ));
+    }
+    let now = now();
+    let membership = json!({
+        "$id": unique_id(),
+        "$createdAt": now,
+        "$updatedAt": now,
+        "userId": user_id,
+        "userName": user["name"],
+        "userEmail": email,
+        "teamId": team,
+        "teamName": team_name,
+        "invited": now,
+        "joined": now,
+        "confirm": true,
+        "roles": roles,
+    });
+    state.memberships.push(membership.clone());
+    let record = find_mut(&mut state.teams, team).unwrap();
+    record["total"] = json!(record["total"].as_u64().unwrap_or_default() + 1);
+    Response::created(membership)
+}
+
+#[tokio::test]
+async fn test_teams() {
+    use appwrite::prelude::*;
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.client();
+    for (id, name) in [("engineering", "Engineering"), ("sales", "Sales")] {
+        TeamsService::create_team(
+            &client,
+            CreateTeamPayload {
+                team_id: TeamId::new(id.to_string()),
+                name: name.to_string(),
+                roles: vec!["owner".to_string()],
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let engineering = TeamId::new("engineering".to_string());
+
+    let teams = TeamsService::list_teams(
+        &client,
+        SearchPayload {
+            search: Some("engin".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(teams.total, 1);
+    assert_eq!(teams.items[0].id, engineering);
+    let team = TeamsService::update_name(&client, &engineering, "R&D")
+        .await
+        .unwrap();
+    assert_eq!(team.name, "R&D");
+
+    let membership = TeamsService::create_membership(
+        &client,
+        &engineering,
+        CreateMembershipPayload {
+            email: "dave@example.com".to_string(),
+            roles: vec!["developer".to_string()],
+            redirect_url: "https://example.com/join".to_string(),
+            name: Some("Dave".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(membership.user_name, "Dave");
+    assert_eq!(membership.team_name, "R&D");
+    let team = TeamsService::get_team(&client, &engineering).await.unwrap();
+    assert_eq!(team.total, 1);
+    let memberships = TeamsService::get_memberships(
+        &client,
+        &engineering,
+        SearchPayload {
+            search: Some("dave".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(memberships.total, 1);
+    let user = UsersService::get_user(&client, &membership.user_id)
+        .await
+        .unwrap();
+    assert_eq!(user.email, "dave@example.com");
+
+    TeamsService::delete_membership(&client, &engineering, &membership.id)
+        .await
+        .unwrap();
+    let team = TeamsService::get_team(&client, &engineering).await.unwrap();
+    assert_eq!(team.total, 0);
+    TeamsService::delete_team(&client, &engineering)
+        .await
+        .unwrap();
+    let error = TeamsService::get_team(&client, &engineering)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::TeamNotFound));
+}
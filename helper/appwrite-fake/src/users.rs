@@ -0,0 +1,301 @@
+use hyper::Method;
+use serde_json::{json, Value};
+
+use crate::{
+    query,
+    request::{now, resolve_id, route_not_found, Handled, Request, Response},
+    state::{field_is, find, find_mut, has_id, State},
+};
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Handled {
+    match (&request.method, request.segments().as_slice()) {
+        (&Method::POST, ["users"]) | (&Method::POST, ["users", "md5"]) => {
+            let user = insert_user(
+                state,
+                &request.require("userId")?,
+                request.string("email"),
+                request.string("phone"),
+                request.string("password"),
+                request.string("name"),
+            )?;
+            Response::created(user)
+        }
+        (&Method::GET, ["users"]) => {
+            query::list("users", state.users.clone(), request, &["name", "email"])
+        }
+        (&Method::GET, ["users", user]) => Response::ok(get_user(state, user)?.clone()),
+        (&Method::DELETE, ["users", user]) => {
+            get_user(state, user)?;
+            delete_user(state, user);
+            Response::no_content()
+        }
+        (&Method::GET, ["users", user, "prefs"]) => {
+            get_user(state, user)?;
+            Response::ok(
+                state
+                    .user_prefs
+                    .get(*user)
+                    .cloned()
+                    .unwrap_or_else(|| json!({})),
+            )
+        }
+        (&Method::PATCH, ["users", user, "prefs"]) => {
+            let prefs = request.json("prefs").cloned().unwrap_or_else(|| json!({}));
+            update_prefs(state, user, prefs)
+        }
+        (&Method::PATCH, ["users", user, "status"]) => {
+            let status = request.bool("status").unwrap_or_default();
+            update_user(state, user, "status", json!(status))
+        }
+        (&Method::PATCH, ["users", user, "verification"]) => {
+            let verified = request.bool("emailVerification").unwrap_or_default();
+            update_user(state, user, "emailVerification", json!(verified))
+        }
+        (&Method::PATCH, ["users", user, "verification", "phone"]) => {
+            let verified = request.bool("phoneVerification").unwrap_or_default();
+            update_user(state, user, "phoneVerification", json!(verified))
+        }
+        (&Method::PATCH, ["users", user, "name"]) => {
+            update_user(state, user, "name", json!(request.require("name")?))
+        }
+        (&Method::PATCH, ["users", user, "password"]) => {
+            update_password(state, user, &request.require("password")?)
+        }
+        (&Method::PATCH, ["users", user, "email"]) => {
+            update_email(state, user, &request.require("email")?)
+        }
+        (&Method::PATCH, ["users", user, "phone"]) => {
+            update_user(state, user, "phone", json!(request.require("phone")?))
+        }
+        (&Method::GET, ["users", user, "sessions"]) => {
+            get_user(state, user)?;
+            let items = state
+                .sessions
+                .iter()
+                .filter(|item| field_is(item, "userId", user))
+                .cloned()
+                .collect();
+            query::list("sessions", items, request, &[])
+        }
+        (&Method::DELETE, ["users", user, "sessions"]) => {
+            get_user(state, user)?;
+            state
+                .sessions
+                .retain(|item| !field_is(item, "userId", user));
+            Response::no_content()
+        }
+        (&Method::DELETE, ["users", user, "sessions", session]) => {
+            get_user(state, user)?;
+            let before = state.sessions.len();
+            state
+                .sessions
+                .retain(|item| !(has_id(item, session) && field_is(item, "userId", user)));
+            if state.sessions.len() == before {
+                return Err(Response::not_found(
+                    "user_session_not_found",
+                    "The current user session could not be found.",
+                ));
+            }
+            Response::no_content()
+        }
+        (&Method::GET, ["users", user, "memberships"]) => {
+            get_user(state, user)?;
+            let items = state
+                .memberships
+                .iter()
+                .filter(|item| field_is(item, "userId", user))
+                .cloned()
+                .collect();
+            query::list("memberships", items, request, &[])
+        }
+        (&Method::GET, ["users", user, "logs"]) => {
+            get_user(state, user)?;
+            Response::ok(json!({ "logs": [], "total": 0 }))
+        }
+        _ => route_not_found(),
+    }
+}
+
+pub(crate) fn user_not_found() -> Response {
+    Response::not_found(
+        "user_not_found",
+        "User with the requested ID could not be found.",
+    )
+}
+
+pub(crate) fn get_user<'a>(state: &'a State, user: &str) -> Result<&'a Value, Response> {
+    find(&state.users, user).ok_or_else(user_not_found)
+}
+
+pub(crate) fn insert_user(
+    state: &mut State,
+    id: &str,
+    email: Option<String>,
+    phone: Option<String>,
+    password: Option<String>,
+    name: Option<String>,
+) -> Result<Value, Response> {
+    let id = resolve_id(id);
+    let email = email.unwrap_or_default().to_lowercase();
+    let phone = phone.unwrap_or_default();
+    let duplicate = find(&state.users, &id).is_some()
+        || (!email.is_empty()
+            && state
+                .users
+                .iter()
+                .any(|user| field_is(user, "email", &email)))
+        || (!phone.is_empty()
+            && state
+                .users
+                .iter()
+                .any(|user| field_is(user, "phone", &phone)));
+    if duplicate {
+        return Err(Response::conflict(
+            "user_already_exists",
+            "A user with the same email already exists in your project.",
+        ));
+    }
+    let now = now();
+    // Appwrite reports an empty `passwordUpdate` for users created without a password.
+    let password_update = if password.is_some() {
+        now.clone()
+    } else {
+        String::new()
+    };
+    let user = json!({
+        "$id": id,
+        "$createdAt": now,
+        "$updatedAt": now,
+        "name": name.unwrap_or_default(),
+        "registration": now,
+        "status": true,
+        "passwordUpdate": password_update,
+        "email": email,
+        "phone": phone,
+        "emailVerification": false,
+        "phoneVerification": false,
+        "prefs": {},
+    });
+    if let Some(password) = password {
+        state.passwords.insert(id.clone(), password);
+    }
+    state.users.push(user.clone());
+    Ok(user)
+}
+
+pub(crate) fn update_user(state: &mut State, user: &str, key: &str, value: Value) -> Handled {
+    let record = find_mut(&mut state.users, user).ok_or_else(user_not_found)?;
+    record[key] = value;
+    record["$updatedAt"] = json!(now());
+    Response::ok(record.clone())
+}
+
+pub(crate) fn update_password(state: &mut State, user: &str, password: &str) -> Handled {
+    get_user(state, user)?;
+    state
+        .passwords
+        .insert(user.to_string(), password.to_string());
+    update_user(state, user, "passwordUpdate", json!(now()))
+}
+
+pub(crate) fn update_email(state: &mut State, user: &str, email: &str) -> Handled {
+    let email = email.to_lowercase();
+    if state
+        .users
+        .iter()
+        .any(|item| field_is(item, "email", &email) && !has_id(item, user))
+    {
+        return Err(Response::conflict(
+            "user_email_already_exists",
+            "A user with the same email already exists in your project.",
+        ));
+    }
+    update_user(state, user, "email", json!(email))
+}
+
+pub(crate) fn update_prefs(state: &mut State, user: &str, prefs: Value) -> Handled {
+    get_user(state, user)?;
+    state.user_prefs.insert(user.to_string(), prefs.clone());
+    update_user(state, user, "prefs", prefs)
+}
+
+fn delete_user(state: &mut State, user: &str) {
+    state.users.retain(|item| !has_id(item, user));
+    state.passwords.remove(user);
+    state.user_prefs.remove(user);
+    state
+        .sessions
+        .retain(|item| !field_is(item, "userId", user));
+    state.jwts.retain(|_, id| id != user);
+    let teams = state
+        .memberships
+        .iter()
+        .filter(|item| field_is(item, "userId", user))
+        .filter_map(|item| item["teamId"].as_str().map(str::to_string))
+        .collect::<Vec<_>>();
+    state
+        .memberships
+        .retain(|item| !field_is(item, "userId", user));
+    for team in teams {
+        if let Some(record) = find_mut(&mut state.teams, &team) {
+            record["total"] = json!(record["total"].as_u64().unwrap_or(1).saturating_sub(1));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_users() {
+    use appwrite::prelude::*;
+
+    let fake = crate::FakeServer::start().await.unwrap();
+    let client = fake.client();
+    for (id, name) in [("alice", "Alice"), ("bob", "Bob")] {
+        UsersService::create_user(
+            &client,
+            UserId::new(id.to_string()),
+            Some(format!("{id}@example.com")),
+            None,
+            Some("password".to_string()),
+            Some(name.to_string()),
+        )
+        .await
+        .unwrap();
+    }
+    let alice = UserId::new("alice".to_string());
+
+    let users = UsersService::list_users(&client, SearchPayload::default())
+        .await
+        .unwrap();
+    assert_eq!(users.total, 2);
+    let users = UsersService::list_users(
+        &client,
+        SearchPayload {
+            search: Some("alice".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(users.total, 1);
+    assert_eq!(users.items[0].id, alice);
+
+    let user = UsersService::update_name(&client, &alice, "Alice Liddell")
+        .await
+        .unwrap();
+    assert_eq!(user.name, "Alice Liddell");
+    let user = UsersService::update_user_status(&client, &alice, false)
+        .await
+        .unwrap();
+    assert!(!user.status);
+    UsersService::update_prefs(&client, &alice, json!({ "theme": "dark" }))
+        .await
+        .unwrap();
+    let prefs: Value = UsersService::get_prefs(&client, &alice).await.unwrap();
+    assert_eq!(prefs, json!({ "theme": "dark" }));
+    let sessions = UsersService::get_sessions(&client, &alice).await.unwrap();
+    assert_eq!(sessions.total, 0);
+
+    UsersService::delete_user(&client, &alice).await.unwrap();
+    let error = UsersService::get_user(&client, &alice).await.unwrap_err();
+    assert_eq!(error.kind(), Some(ApiErrorKind::UserNotFound));
+}
@@ -23,14 +23,10 @@ struct GenericParams {
 struct AttrField {
     pub ident: Option<syn::Ident>,
     pub ty: syn::Type,
-    #[darling(default)]
     pub default: Option<syn::LitStr>,
-    #[darling(default)]
     pub min: Option<syn::LitStr>,
-    #[darling(default)]
     pub max: Option<syn::LitStr>,
 
-    #[darling(default)]
     pub parse: Option<syn::LitStr>,
 }
 
@@ -0,0 +1,121 @@
+extern crate proc_macro;
+
+use quote::quote;
+use syn::{parse_quote, FnArg, GenericParam, ImplItem, ItemImpl, Pat, Path, ReturnType, Type};
+
+#[proc_macro_attribute]
+pub fn blocking_service(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    // `#[blocking_service(path::to::blocking::Service)]` adds methods to a blocking service that
+    // another impl block already declared, instead of declaring a new one.
+    let extends = match attr.is_empty() {
+        true => None,
+        false => Some(syn::parse_macro_input!(attr as Path)),
+    };
+    let item = syn::parse_macro_input!(input as ItemImpl);
+    let service = match *item.self_ty {
+        Type::Path(ref path) => path.path.segments.last().unwrap().ident.clone(),
+        _ => panic!("#[blocking_service] only works on impl blocks of named types"),
+    };
+
+    let mut methods = vec![];
+    for impl_item in item.items.iter() {
+        let method = match impl_item {
+            ImplItem::Method(method) if method.sig.asyncness.is_some() => method,
+            _ => continue,
+        };
+        let ident = &method.sig.ident;
+        let mut sig = method.sig.clone();
+        sig.asyncness = None;
+
+        let mut args = vec![];
+        for arg in sig.inputs.iter_mut() {
+            let arg = match arg {
+                FnArg::Typed(arg) => arg,
+                FnArg::Receiver(_) => {
+                    panic!("#[blocking_service] only works on associated functions")
+                }
+            };
+            let name = match *arg.pat {
+                Pat::Ident(ref pat) => pat.ident.clone(),
+                _ => panic!("#[blocking_service] requires plain argument names"),
+            };
+            if name == "client" {
+                arg.ty = parse_quote!(&crate::blocking::BlockingAppWriteClient);
+                args.push(quote! { client.inner() });
+            } else {
+                args.push(quote! { #name });
+            }
+        }
+
+        let type_params = sig
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let turbofish = if type_params.is_empty() {
+            quote! {}
+        } else {
+            quote! { ::<#(#type_params),*> }
+        };
+        let call = quote! { super::#service::#ident #turbofish(#(#args),*) };
+
+        // Streaming responses can't outlive the runtime call, so they are read into memory.
+        let returns_response = match sig.output {
+            ReturnType::Type(_, ref ty) => quote!(#ty)
+                .to_string()
+                .replace(' ', "")
+                .contains("reqwest::Response"),
+            ReturnType::Default => false,
+        };
+        let body = if returns_response {
+            sig.output = parse_quote!(-> Result<::bytes::Bytes, crate::error::Error>);
+            quote! {
+                client.block_on(async move {
+                    let response = #call.await?;
+                    Ok(response.bytes().await?)
+                })
+            }
+        } else {
+            quote! { client.block_on(#call) }
+        };
+
+        methods.push(quote! {
+            pub #sig {
+                #body
+            }
+        });
+    }
+
+    let blocking = match extends {
+        Some(path) => quote! {
+            impl #path {
+                #(#methods)*
+            }
+        },
+        None => quote! {
+            pub struct #service;
+
+            impl #service {
+                #(#methods)*
+            }
+        },
+    };
+
+    quote! {
+        #item
+
+        pub(crate) mod blocking {
+            use super::*;
+
+            #blocking
+        }
+    }
+    .into()
+}
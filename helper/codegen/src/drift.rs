@@ -0,0 +1,320 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use syn::{Attribute, Fields, Item, Lit, Meta, NestedMeta, Type};
+
+use crate::{
+    generate::{camel_case, Manifest, Result},
+    spec::Spec,
+};
+
+pub const MODELS: &str = "src/models";
+
+#[derive(Debug)]
+pub struct Drift {
+    pub definition: String,
+    pub model: String,
+    pub message: String,
+}
+
+// The wire names a hand-written struct reads, as far as its serde attributes tell.
+#[derive(Debug, Default)]
+struct Model {
+    fields: BTreeMap<String, bool>,
+    // A flattened field swallows whatever the named fields don't.
+    open: bool,
+}
+
+// Compares every definition mapped under [models] with the struct it maps to. Definitions whose
+// model isn't a plain struct (generics, hand-written `Deserialize` impls) are returned unchecked.
+pub fn check(root: &Path, spec: &Spec, manifest: &Manifest) -> Result<(Vec<Drift>, Vec<String>)> {
+    let models = read_models(&root.join(MODELS))?;
+    let mut drift = vec![];
+    let mut unchecked = vec![];
+    // Lists are `ListResponse<T>`, which is checked through its item definition.
+    let mapped = manifest
+        .models
+        .iter()
+        .filter(|(_, target)| !target.starts_with("ListResponse<"));
+    for (definition, target) in mapped {
+        let schema = match spec.definitions.get(definition) {
+            Some(schema) => schema,
+            None => {
+                drift.push(Drift {
+                    definition: definition.clone(),
+                    model: target.clone(),
+                    message: "the spec has no such definition".to_string(),
+                });
+                continue;
+            }
+        };
+        // `attributeEmail` maps to the `Attribute` enum, whose variants hold `AttributeEmail`.
+        let name = [target.clone(), pascal_case(definition)]
+            .into_iter()
+            .find_map(|name| {
+                models
+                    .keys()
+                    .find(|model| model.eq_ignore_ascii_case(&name))
+                    .cloned()
+            });
+        let Some(name) = name else {
+            unchecked.push(definition.clone());
+            continue;
+        };
+        let model = &models[&name];
+        let mut push = |message: String| {
+            drift.push(Drift {
+                definition: definition.clone(),
+                model: name.clone(),
+                message,
+            })
+        };
+        for key in schema.properties.keys() {
+            if !model.open && !model.fields.contains_key(key) {
+                push(format!("drops `{}`", key));
+            }
+        }
+        for key in model
+            .fields
+            .iter()
+            .filter(|(_, required)| **required)
+            .map(|(key, _)| key)
+        {
+            if !schema.properties.contains_key(key) {
+                push(format!(
+                    "requires `{}`, which the spec does not define",
+                    key
+                ));
+            } else if !schema.required.contains(key) {
+                push(format!("requires `{}`, which the spec marks optional", key));
+            }
+        }
+    }
+    Ok((drift, unchecked))
+}
+
+fn read_models(dir: &Path) -> Result<BTreeMap<String, Model>> {
+    let mut structs = BTreeMap::new();
+    // Variant payload -> tags of the internally tagged enums it sits in, and whether serde
+    // requires them; hand-written impls like `AttributeStringLike`'s fill theirs in.
+    let mut tags: BTreeMap<String, Vec<(String, bool)>> = BTreeMap::new();
+    let mut enums = vec![];
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| format!("{}: {}", dir.display(), e))?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths
+        .iter()
+        .filter(|path| path.extension() == Some("rs".as_ref()))
+    {
+        let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let file = syn::parse_file(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for item in file.items {
+            match item {
+                Item::Struct(item) if item.generics.params.is_empty() => {
+                    if let Some(model) = model(&item.attrs, &item.fields) {
+                        structs.insert(item.ident.to_string(), model);
+                    }
+                }
+                Item::Enum(item) => {
+                    let tag = serde_args(&item.attrs).remove("tag");
+                    let payloads = item
+                        .variants
+                        .iter()
+                        .filter_map(|variant| match &variant.fields {
+                            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                                type_name(&fields.unnamed[0].ty)
+                            }
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    if let Some(tag) = tag {
+                        let required = derives(&item.attrs, "Deserialize");
+                        enums.push((item.ident.to_string(), (tag, required), payloads));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    // Tags are inherited through nested enums, so keep going until nothing changes.
+    loop {
+        let mut changed = false;
+        for (name, tag, payloads) in enums.iter() {
+            let mut inherited = tags.get(name).cloned().unwrap_or_default();
+            inherited.push(tag.clone());
+            for payload in payloads {
+                let entry = tags.entry(payload.clone()).or_default();
+                for tag in inherited.iter() {
+                    if !entry.contains(tag) {
+                        entry.push(tag.clone());
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    for (name, tags) in tags {
+        if let Some(model) = structs.get_mut(&name) {
+            model.fields.extend(tags);
+        }
+    }
+    Ok(structs)
+}
+
+// None for structs serde doesn't see field by field.
+fn model(attrs: &[Attribute], fields: &Fields) -> Option<Model> {
+    if !derives(attrs, "Deserialize") {
+        return None;
+    }
+    let Fields::Named(fields) = fields else {
+        return None;
+    };
+    let container = serde_args(attrs);
+    let camel = container.get("rename_all").map(String::as_str) == Some("camelCase");
+    let mut model = Model::default();
+    for field in fields.named.iter() {
+        let args = serde_args(&field.attrs);
+        if args.contains_key("skip") || args.contains_key("skip_deserializing") {
+            continue;
+        }
+        if args.contains_key("flatten") {
+            model.open = true;
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap().to_string();
+        let ident = ident.trim_start_matches("r#");
+        let key = match args.get("rename") {
+            Some(rename) => rename.clone(),
+            None if camel => camel_case(ident),
+            None => ident.to_string(),
+        };
+        let optional = type_name(&field.ty).as_deref() == Some("Option");
+        let required = !optional && !args.contains_key("default");
+        model.fields.insert(key, required);
+    }
+    Some(model)
+}
+
+fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(meta) => meta.path().segments.last().unwrap().ident == name,
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+// `#[serde(...)]` arguments; bare flags map to an empty string.
+fn serde_args(attrs: &[Attribute]) -> BTreeMap<String, String> {
+    let mut args = BTreeMap::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            continue;
+        };
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    if let Some(ident) = path.get_ident() {
+                        args.insert(ident.to_string(), String::new());
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(pair)) => {
+                    if let (Some(ident), Lit::Str(value)) = (pair.path.get_ident(), &pair.lit) {
+                        args.insert(ident.to_string(), value.value());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    args
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[test]
+fn test_drift() {
+    let root = std::env::temp_dir().join(format!("appwrite-codegen-drift-{}", std::process::id()));
+    fs::create_dir_all(root.join(MODELS)).unwrap();
+    fs::write(
+        root.join(MODELS).join("user.rs"),
+        r#"
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct User {
+            #[serde(rename = "$id")]
+            pub id: UserId,
+            pub name: String,
+            pub email_verification: bool,
+            #[serde(default)]
+            pub prefs: serde_json::Value,
+            pub phone: Option<String>,
+        }
+        "#,
+    )
+    .unwrap();
+    let spec: Spec = serde_json::from_value(serde_json::json!({
+        "paths": {},
+        "definitions": {
+            "user": {
+                "type": "object",
+                "properties": {
+                    "$id": { "type": "string" },
+                    "name": { "type": "string" },
+                    "status": { "type": "boolean" },
+                    "prefs": { "type": "object" },
+                    "phone": { "type": "string" }
+                },
+                "required": ["$id", "status"]
+            }
+        }
+    }))
+    .unwrap();
+    let manifest: Manifest = toml::from_str(
+        "[services]\n[models]\nuser = \"User\"\nuserList = \"ListResponse<User>\"\nfloat = \"Attribute\"",
+    )
+    .unwrap();
+    let (drift, unchecked) = check(&root, &spec, &manifest).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let messages = drift
+        .iter()
+        .map(|drift| format!("{}: {}", drift.model, drift.message))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Attribute: the spec has no such definition",
+            "User: drops `status`",
+            "User: requires `emailVerification`, which the spec does not define",
+            "User: requires `name`, which the spec marks optional",
+        ]
+    );
+    assert!(unchecked.is_empty());
+}
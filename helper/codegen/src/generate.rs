@@ -0,0 +1,647 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    drift::{self, Drift},
+    spec::{Operation, Parameter, Schema, Spec},
+};
+
+pub const SPEC: &str = "helper/codegen/spec/swagger2-1.2.x-server.json";
+pub const MANIFEST: &str = "helper/codegen/overrides.toml";
+pub const OUTPUT: &str = "src/services/generated";
+
+pub type Result<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub services: BTreeMap<String, ServiceEntry>,
+    #[serde(default)]
+    pub ids: BTreeMap<String, String>,
+    #[serde(default)]
+    pub models: BTreeMap<String, String>,
+    #[serde(default)]
+    pub overrides: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServiceEntry {
+    pub service: String,
+    pub module: String,
+}
+
+#[derive(Debug)]
+pub struct Binding {
+    pub key: String,
+    pub method: String,
+    pub path: String,
+    pub target: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Output {
+    pub files: BTreeMap<PathBuf, String>,
+    pub generated: Vec<Binding>,
+    pub overridden: Vec<Binding>,
+    // Endpoints under a tag [services] doesn't map, so neither generated nor hand-written.
+    pub unwrapped: Vec<Binding>,
+    pub models: Vec<String>,
+    pub drift: Vec<Drift>,
+    pub unchecked: Vec<String>,
+    pub stand_in: Option<String>,
+}
+
+struct Endpoint<'a> {
+    key: String,
+    method: String,
+    path: String,
+    operation: &'a Operation,
+}
+
+enum Returns {
+    Nothing,
+    Raw,
+    Model(String),
+}
+
+pub fn generate(root: &Path) -> Result<Output> {
+    let spec = read(&root.join(SPEC))?;
+    let spec: Spec = serde_json::from_str(&spec).map_err(|e| format!("{}: {}", SPEC, e))?;
+    let manifest = read(&root.join(MANIFEST))?;
+    let manifest: Manifest =
+        toml::from_str(&manifest).map_err(|e| format!("{}: {}", MANIFEST, e))?;
+    let mut output = generate_from(root, &spec, &manifest)?;
+    (output.drift, output.unchecked) = drift::check(root, &spec, &manifest)?;
+    output.stand_in = spec.stand_in.clone();
+    Ok(output)
+}
+
+pub fn generate_from(root: &Path, spec: &Spec, manifest: &Manifest) -> Result<Output> {
+    let mut tags: BTreeMap<&str, Vec<Endpoint>> = BTreeMap::new();
+    for (path, operations) in spec.paths.iter() {
+        for (method, operation) in operations.iter() {
+            let tag = operation
+                .tags
+                .first()
+                .ok_or_else(|| format!("{} {} has no tag", method, path))?;
+            tags.entry(tag).or_default().push(Endpoint {
+                key: format!("{}.{}", tag, operation.appwrite.method),
+                method: method.to_uppercase(),
+                path: path.clone(),
+                operation,
+            });
+        }
+    }
+    for key in manifest.overrides.keys() {
+        if !tags.values().flatten().any(|endpoint| &endpoint.key == key) {
+            return Err(format!(
+                "{} overrides {}, which is not in the spec",
+                MANIFEST, key
+            ));
+        }
+    }
+
+    let mut output = Output::default();
+    let mut modules = vec![];
+    let mut exports = BTreeMap::new();
+    for (tag, endpoints) in tags {
+        let Some(entry) = manifest.services.get(tag) else {
+            output
+                .unwrapped
+                .extend(endpoints.iter().map(|endpoint| Binding {
+                    key: endpoint.key.clone(),
+                    method: endpoint.method.clone(),
+                    path: endpoint.path.clone(),
+                    target: "-".to_string(),
+                }));
+            continue;
+        };
+        let source_path = format!("src/services/{}.rs", entry.module);
+        let source = read(&root.join(&source_path))?;
+
+        let mut generator = Generator::new(spec, manifest);
+        let mut methods = String::new();
+        for endpoint in endpoints.iter() {
+            if let Some(target) = manifest.overrides.get(&endpoint.key) {
+                if !defines_fn(&source, target) {
+                    return Err(format!(
+                        "{} is overridden by {}::{}, which is not defined in {}",
+                        endpoint.key, entry.service, target, source_path
+                    ));
+                }
+                output.overridden.push(Binding {
+                    key: endpoint.key.clone(),
+                    method: endpoint.method.clone(),
+                    path: endpoint.path.clone(),
+                    target: format!("{}::{}", entry.service, target),
+                });
+                continue;
+            }
+            let name = snake_case(&endpoint.operation.appwrite.method);
+            if defines_fn(&source, &name) {
+                return Err(format!(
+                    "{} would generate {}::{}, which {} already defines; list it under [overrides]",
+                    endpoint.key, entry.service, name, source_path
+                ));
+            }
+            generator.method(endpoint, &name, &mut methods)?;
+            output.generated.push(Binding {
+                key: endpoint.key.clone(),
+                method: endpoint.method.clone(),
+                path: endpoint.path.clone(),
+                target: format!("{}::{}", entry.service, name),
+            });
+        }
+        if methods.is_empty() {
+            continue;
+        }
+
+        let mut code = header(&entry.module);
+        code.push_str(
+            "use reqwest::Method;\n\n\
+             use crate::{check_response, client::RequestData, prelude::*};\n\n",
+        );
+        for item in generator.payloads.values().chain(generator.models.values()) {
+            code.push_str(item);
+        }
+        let _ = writeln!(
+            code,
+            "#[cfg_attr(feature = \"blocking\", \
+             blocking_macro::blocking_service(crate::services::{}::blocking::{}))]",
+            entry.module, entry.service
+        );
+        code.push_str("#[cfg_attr(feature = \"tracing\", trace_macro::instrument_service)]\n");
+        let _ = writeln!(code, "impl {} {{\n{}}}", entry.service, methods);
+
+        let names = generator
+            .payloads
+            .keys()
+            .chain(generator.models.keys())
+            .cloned()
+            .collect::<Vec<_>>();
+        output.models.extend(generator.models.keys().cloned());
+        if !names.is_empty() {
+            exports.insert(entry.module.clone(), names);
+        }
+        modules.push(entry.module.clone());
+        output.files.insert(
+            Path::new(OUTPUT).join(format!("{}.rs", entry.module)),
+            rustfmt(&code)?,
+        );
+    }
+
+    let mut code = header("mod");
+    for module in modules.iter() {
+        let _ = writeln!(code, "pub mod {};", module);
+    }
+    code.push_str("\npub mod prelude {\n");
+    for (module, names) in exports.iter() {
+        let _ = writeln!(code, "pub use super::{}::{{{}}};", module, names.join(", "));
+    }
+    code.push_str("}\n");
+    output
+        .files
+        .insert(Path::new(OUTPUT).join("mod.rs"), rustfmt(&code)?);
+    Ok(output)
+}
+
+struct Generator<'a> {
+    spec: &'a Spec,
+    manifest: &'a Manifest,
+    payloads: IndexMap<String, String>,
+    models: IndexMap<String, String>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(spec: &'a Spec, manifest: &'a Manifest) -> Self {
+        Generator {
+            spec,
+            manifest,
+            payloads: IndexMap::new(),
+            models: IndexMap::new(),
+        }
+    }
+
+    fn method(&mut self, endpoint: &Endpoint, name: &str, out: &mut String) -> Result<()> {
+        let operation = endpoint.operation;
+        let mut args = vec!["client: &AppWriteClient".to_string()];
+        let mut url = endpoint.path.clone();
+        let mut query = String::new();
+        let mut body = None;
+        for parameter in operation.parameters.iter() {
+            let ident = field_name(&parameter.name);
+            match parameter.location.as_str() {
+                "path" => {
+                    let ty = self
+                        .manifest
+                        .ids
+                        .get(&parameter.name)
+                        .map(|id| format!("&{}", id))
+                        .unwrap_or_else(|| "&str".to_string());
+                    args.push(format!("{}: {}", ident, ty));
+                    url = url.replace(
+                        &format!("{{{}}}", parameter.name),
+                        &format!("{{{}}}", ident),
+                    );
+                }
+                "query" => {
+                    let ty = self.param_type(&parameter.name, &parameter.schema);
+                    args.push(if parameter.required {
+                        format!("{}: {}", ident, ty)
+                    } else {
+                        format!("{}: Option<{}>", ident, ty)
+                    });
+                    query_param(parameter, &ident, &mut query);
+                }
+                "body" => {
+                    let schema = parameter.schema.schema.as_deref().unwrap_or(&parameter.schema);
+                    let payload = format!("{}Payload", pascal_case(name));
+                    self.payload(&payload, schema);
+                    args.push(format!("payload: {}", payload));
+                    body = Some(payload);
+                }
+                _ => {
+                    return Err(format!(
+                        "{} takes {} parameters, which need a hand-written binding; list it under [overrides]",
+                        endpoint.key, parameter.location
+                    ))
+                }
+            }
+        }
+        let returns = self.returns(endpoint)?;
+
+        if args.len() > 7 {
+            out.push_str("#[allow(clippy::too_many_arguments)]\n");
+        }
+        let ty = match returns {
+            Returns::Nothing => "()".to_string(),
+            Returns::Raw => "reqwest::Response".to_string(),
+            Returns::Model(ref ty) => ty.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "pub async fn {}({}) -> Result<{}, crate::error::Error> {{",
+            name,
+            args.join(", "),
+            ty
+        );
+        let url_arg = if url.contains('{') {
+            let _ = writeln!(out, "let url = format!(\"{}\");", url);
+            "&url"
+        } else {
+            let _ = writeln!(out, "let url = \"{}\";", url);
+            "url"
+        };
+        let data = if body.is_some() {
+            "RequestData::Json(serde_json::to_value(payload)?)"
+        } else if !query.is_empty() {
+            out.push_str("let mut params = vec![];\n");
+            out.push_str(&query);
+            "RequestData::Params(params)"
+        } else {
+            "RequestData::None"
+        };
+        let _ = writeln!(
+            out,
+            "let response = client.call(Method::{}, {}, {}).await?;",
+            endpoint.method, url_arg, data
+        );
+        match returns {
+            Returns::Nothing => out.push_str("Ok(check_response!(response))\n"),
+            Returns::Raw => out.push_str("check_response!(response);\nOk(response)\n"),
+            Returns::Model(ty) => {
+                let _ = writeln!(out, "Ok(check_response!({}: response))", ty);
+            }
+        }
+        out.push_str("}\n\n");
+        Ok(())
+    }
+
+    fn returns(&mut self, endpoint: &Endpoint) -> Result<Returns> {
+        let (_, response) = endpoint
+            .operation
+            .responses
+            .iter()
+            .find(|(status, _)| status.starts_with('2'))
+            .ok_or_else(|| format!("{} has no successful response", endpoint.key))?;
+        Ok(match response.schema {
+            None => Returns::Nothing,
+            Some(ref schema) if schema.kind.as_deref() == Some("file") => Returns::Raw,
+            Some(ref schema) => Returns::Model(self.model_type(schema)),
+        })
+    }
+
+    fn payload(&mut self, name: &str, schema: &Schema) {
+        let mut code = String::new();
+        code.push_str("#[derive(Debug, Clone, Serialize)]\n#[serde(rename_all = \"camelCase\")]\n");
+        let _ = writeln!(code, "pub struct {} {{", name);
+        for (key, property) in schema.properties.iter() {
+            let ty = self.param_type(key, property);
+            if schema.required.contains(key) {
+                field(&mut code, key, &ty, None);
+            } else {
+                field(
+                    &mut code,
+                    key,
+                    &format!("Option<{}>", ty),
+                    Some("skip_serializing_if = \"Option::is_none\""),
+                );
+            }
+        }
+        code.push_str("}\n\n");
+        self.payloads.insert(name.to_string(), code);
+    }
+
+    fn param_type(&self, name: &str, schema: &Schema) -> String {
+        match (name, schema.kind.as_deref()) {
+            ("permissions", Some("array")) => "Vec<Permission>".to_string(),
+            ("queries", Some("array")) => "Vec<Query>".to_string(),
+            (_, Some("string")) => self
+                .manifest
+                .ids
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| "String".to_string()),
+            (_, Some("array")) => {
+                let item = schema.items.as_deref().map_or_else(
+                    || "serde_json::Value".to_string(),
+                    |items| self.param_type("", items),
+                );
+                format!("Vec<{}>", item)
+            }
+            (_, kind) => primitive(kind).to_string(),
+        }
+    }
+
+    fn model_type(&mut self, schema: &Schema) -> String {
+        if let Some(definition) = schema.definition() {
+            return self.definition_type(definition);
+        }
+        if let Some(ref one_of) = schema.one_of {
+            let types = one_of
+                .iter()
+                .map(|schema| self.model_type(schema))
+                .collect::<Vec<_>>();
+            return match types.first() {
+                Some(first) if types.iter().all(|ty| ty == first) => first.clone(),
+                _ => "serde_json::Value".to_string(),
+            };
+        }
+        match schema.kind.as_deref() {
+            Some("array") => {
+                let item = schema.items.as_deref().map_or_else(
+                    || "serde_json::Value".to_string(),
+                    |items| self.model_type(items),
+                );
+                format!("Vec<{}>", item)
+            }
+            kind => primitive(kind).to_string(),
+        }
+    }
+
+    // Definitions without a hand-written model become plain structs in the generated module.
+    fn definition_type(&mut self, definition: &str) -> String {
+        if let Some(model) = self.manifest.models.get(definition) {
+            return model.clone();
+        }
+        let name = pascal_case(&snake_case(definition));
+        if self.models.contains_key(&name) {
+            return name;
+        }
+        let schema = match self.spec.definitions.get(definition) {
+            Some(schema) if !schema.properties.is_empty() => schema,
+            _ => return "serde_json::Value".to_string(),
+        };
+        self.models.insert(name.clone(), String::new());
+        let mut code = String::new();
+        code.push_str(
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(rename_all = \"camelCase\")]\n",
+        );
+        let _ = writeln!(code, "pub struct {} {{", name);
+        for (key, property) in schema.properties.iter() {
+            let ty = self.model_type(property);
+            if schema.required.contains(key) && !property.nullable {
+                field(&mut code, key, &ty, None);
+            } else {
+                field(
+                    &mut code,
+                    key,
+                    &format!("Option<{}>", ty),
+                    Some("default, skip_serializing_if = \"Option::is_none\""),
+                );
+            }
+        }
+        code.push_str("}\n\n");
+        self.models.insert(name.clone(), code);
+        name
+    }
+}
+
+fn query_param(parameter: &Parameter, ident: &str, out: &mut String) {
+    let name = &parameter.name;
+    let push = if parameter.schema.kind.as_deref() == Some("array") {
+        format!(
+            "for value in {} {{ params.push((\"{}[]\".into(), value.to_string())); }}",
+            ident, name
+        )
+    } else {
+        format!("params.push((\"{}\".into(), {}.to_string()));", name, ident)
+    };
+    if parameter.required {
+        let _ = writeln!(out, "{}", push);
+    } else {
+        let _ = writeln!(out, "if let Some({0}) = {0} {{ {1} }}", ident, push);
+    }
+}
+
+fn field(out: &mut String, key: &str, ty: &str, serde: Option<&str>) {
+    let ident = field_name(key);
+    let plain = ident.trim_start_matches("r#");
+    let mut attributes = vec![];
+    if camel_case(plain) != key {
+        attributes.push(format!("rename = \"{}\"", key));
+    }
+    attributes.extend(serde.map(str::to_string));
+    if !attributes.is_empty() {
+        let _ = writeln!(out, "#[serde({})]", attributes.join(", "));
+    }
+    let _ = writeln!(out, "pub {}: {},", ident, ty);
+}
+
+fn primitive(kind: Option<&str>) -> &'static str {
+    match kind {
+        Some("string") => "String",
+        Some("integer") => "i64",
+        Some("number") => "f64",
+        Some("boolean") => "bool",
+        _ => "serde_json::Value",
+    }
+}
+
+fn header(module: &str) -> String {
+    format!(
+        "// @generated by `cargo run -p codegen` from {}.\n\
+         // Do not edit by hand: put hand-written bindings in src/services/{}.rs and list them in\n\
+         // {}.\n\n",
+        SPEC,
+        if module == "mod" { "<service>" } else { module },
+        MANIFEST
+    )
+}
+
+fn defines_fn(source: &str, name: &str) -> bool {
+    source.contains(&format!("fn {}(", name)) || source.contains(&format!("fn {}<", name))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn rustfmt(code: &str) -> Result<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(code.as_bytes())
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "rustfmt rejected the generated code:\n{}\n{}",
+            String::from_utf8_lossy(&output.stderr),
+            code
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+fn field_name(key: &str) -> String {
+    let name = snake_case(key.trim_start_matches('$'));
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+pub fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let boundary = match i.checked_sub(1).map(|j| chars[j]) {
+                Some(prev) if prev.is_ascii_lowercase() || prev.is_ascii_digit() => true,
+                // The last capital of an acronym starts the next word: `createPHPassUser`.
+                Some(prev) if prev.is_ascii_uppercase() => chars
+                    .get(i + 1)
+                    .is_some_and(|next| next.is_ascii_lowercase()),
+                _ => false,
+            };
+            if boundary {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c == '-' {
+            out.push('_');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub fn camel_case(snake: &str) -> String {
+    let pascal = pascal_case(snake);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(snake_case("createMD5User"), "create_md5_user");
+    assert_eq!(snake_case("createPHPassUser"), "create_ph_pass_user");
+    assert_eq!(snake_case("listCountriesEU"), "list_countries_eu");
+    assert_eq!(pascal_case(&snake_case("createSHAUser")), "CreateShaUser");
+    assert_eq!(field_name("$createdAt"), "created_at");
+    assert_eq!(field_name("type"), "r#type");
+    assert_eq!(camel_case("password_cpu"), "passwordCpu");
+}
+
+#[test]
+fn test_unmapped_definitions_become_models() {
+    let root = std::env::temp_dir().join(format!("appwrite-codegen-{}", std::process::id()));
+    fs::create_dir_all(root.join("src/services")).unwrap();
+    fs::write(root.join("src/services/accounts.rs"), "").unwrap();
+    let spec: Spec = serde_json::from_value(serde_json::json!({
+        "paths": {
+            "/account/jwt": {
+                "post": {
+                    "tags": ["account"],
+                    "x-appwrite": { "method": "createJWT" },
+                    "responses": { "201": { "schema": { "$ref": "#/definitions/jwt" } } }
+                }
+            }
+        },
+        "definitions": {
+            "jwt": {
+                "type": "object",
+                "properties": { "jwt": { "type": "string" }, "$createdAt": { "type": "string" } },
+                "required": ["jwt"]
+            }
+        }
+    }))
+    .unwrap();
+    let manifest: Manifest = toml::from_str(
+        "[services]\naccount = { service = \"AccountsService\", module = \"accounts\" }",
+    )
+    .unwrap();
+    let output = generate_from(&root, &spec, &manifest).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(output.models, vec!["Jwt".to_string()]);
+    let code = &output.files[&Path::new(OUTPUT).join("accounts.rs")];
+    assert!(code.contains("pub struct Jwt {"));
+    assert!(code.contains("rename = \"$createdAt\",\n        default,"));
+    assert!(code.contains("pub created_at: Option<String>,\n    pub jwt: String,"));
+    assert!(code.contains("pub async fn create_jwt(client: &AppWriteClient) -> Result<Jwt"));
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+// Only the parts of Appwrite's swagger2 spec the generator looks at.
+#[derive(Debug, Deserialize)]
+pub struct Spec {
+    // Set only in the hand-written stand-in, never by upstream; see main.rs.
+    #[serde(rename = "x-stand-in")]
+    pub stand_in: Option<String>,
+    pub paths: IndexMap<String, IndexMap<String, Operation>>,
+    pub definitions: IndexMap<String, Schema>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Operation {
+    pub tags: Vec<String>,
+    #[serde(rename = "x-appwrite")]
+    pub appwrite: AppwriteExtension,
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    pub responses: BTreeMap<String, Response>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AppwriteExtension {
+    pub method: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(rename = "in")]
+    pub location: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(flatten)]
+    pub schema: Schema,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Response {
+    pub schema: Option<Schema>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Schema {
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    #[serde(rename = "x-oneOf")]
+    pub one_of: Option<Vec<Schema>>,
+    pub items: Option<Box<Schema>>,
+    // Only body parameters carry a nested schema; everything else is described inline.
+    pub schema: Option<Box<Schema>>,
+    #[serde(default)]
+    pub properties: IndexMap<String, Schema>,
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(rename = "x-nullable", default)]
+    pub nullable: bool,
+}
+
+impl Schema {
+    pub fn definition(&self) -> Option<&str> {
+        self.reference
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    }
+}
@@ -9,7 +9,6 @@ use quote::quote;
 struct Params {
     pub data: ast::Data<util::Ignored, ParamField>,
     pub generics: ast::Generics<ast::GenericParam<GenericParams>>,
-    #[darling(default)]
     pub rename_all: Option<syn::LitStr>,
 }
 
@@ -24,7 +23,6 @@ struct GenericParams {
 struct ParamField {
     pub ident: Option<syn::Ident>,
     pub ty: syn::Type,
-    #[darling(default)]
     pub rename: Option<syn::LitStr>,
 }
 
@@ -0,0 +1,59 @@
+extern crate proc_macro;
+
+use quote::{format_ident, quote};
+use syn::{parse_quote, FnArg, ImplItem, ItemImpl, Pat, Type};
+
+#[proc_macro_attribute]
+pub fn instrument_service(
+    _attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut item = syn::parse_macro_input!(input as ItemImpl);
+    let service = match *item.self_ty {
+        Type::Path(ref path) => path.path.segments.last().unwrap().ident.to_string(),
+        _ => panic!("#[instrument_service] only works on impl blocks of named types"),
+    };
+
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Method(method) if method.sig.asyncness.is_some() => method,
+            _ => continue,
+        };
+        let name = format!("{}::{}", service, method.sig.ident);
+        let mut fields = vec![];
+        for arg in method.sig.inputs.iter() {
+            let ident = match arg {
+                FnArg::Typed(arg) => match *arg.pat {
+                    Pat::Ident(ref pat) => &pat.ident,
+                    _ => continue,
+                },
+                FnArg::Receiver(_) => continue,
+            };
+            let arg_name = ident.to_string();
+            if arg_name == "client" {
+                fields.push(quote! { appwrite.project_id = #ident.get_project_id() });
+            } else if arg_name.ends_with("_id") {
+                let field = format_ident!("{}", arg_name);
+                fields.push(quote! { appwrite.#field = %#ident });
+            }
+        }
+        method.attrs.push(parse_quote! {
+            #[::tracing::instrument(
+                name = #name,
+                skip_all,
+                fields(
+                    #(#fields,)*
+                    otel.kind = "client",
+                    otel.status_code = ::tracing::field::Empty,
+                    http.method = ::tracing::field::Empty,
+                    http.url = ::tracing::field::Empty,
+                    http.status_code = ::tracing::field::Empty,
+                    http.request_content_length = ::tracing::field::Empty,
+                    http.response_content_length = ::tracing::field::Empty,
+                )
+            )]
+        });
+    }
+
+    quote!(#item).into()
+}
@@ -0,0 +1,102 @@
+use std::{future::Future, sync::Arc};
+
+use crate::{
+    client::{AppWriteClient, AppWriteClientBuilder},
+    error::Error,
+};
+
+pub use crate::services::{
+    accounts::blocking::AccountsService, avatars::blocking::AvatarService,
+    databases::blocking::DatabasesService, functions::blocking::FunctionsService,
+    health::blocking::HealthService, locales::blocking::LocalesService,
+    storages::blocking::StoragesService, teams::blocking::TeamsService,
+    users::blocking::UsersService,
+};
+
+#[derive(Debug, Clone)]
+pub struct BlockingAppWriteClient {
+    inner: AppWriteClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingAppWriteClient {
+    pub fn builder(host_url: &str, project_id: &str) -> AppWriteClientBuilder {
+        AppWriteClientBuilder::new(host_url, project_id)
+    }
+
+    pub fn new(client: AppWriteClient) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(BlockingAppWriteClient {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    pub fn inner(&self) -> &AppWriteClient {
+        &self.inner
+    }
+
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl AppWriteClientBuilder {
+    pub fn build_blocking(self) -> Result<BlockingAppWriteClient, Error> {
+        BlockingAppWriteClient::new(self.build()?)
+    }
+}
+
+#[test]
+fn test_blocking_client() {
+    use crate::{models::prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 5, "status": "pass" }),
+    );
+    transport.push_response(StatusCode::OK, Default::default(), "png");
+    let client = BlockingAppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build_blocking()
+        .unwrap();
+    let health = HealthService::get_http(&client).unwrap();
+    assert_eq!(health.ping, 5);
+    let qr = AvatarService::get_qr(&client, "data".into(), None, None, None).unwrap();
+    assert_eq!(&qr[..], b"png");
+
+    let path = std::env::temp_dir().join(format!(
+        "appwrite-blocking-upload-{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, b"hello").unwrap();
+    let file = serde_json::json!({
+        "$id": "f1",
+        "bucketId": "b1",
+        "$createdAt": "2023-01-01T00:00:00.000+00:00",
+        "$updatedAt": "2023-01-01T00:00:00.000+00:00",
+        "$permissions": [],
+        "name": "appwrite-blocking-upload.txt",
+        "signature": "",
+        "mimeType": "text/plain",
+        "sizeOriginal": 5,
+        "chunksTotal": 1,
+        "chunksUploaded": 1,
+    });
+    transport.push_json(StatusCode::CREATED, file);
+    let input = InputFile::from_path_blocking(&path).unwrap();
+    let uploaded = StoragesService::create_file(
+        &client,
+        &BucketId::new("b1".into()),
+        &FileId::new("f1".into()),
+        input,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(uploaded.id, FileId::new("f1".into()));
+    std::fs::remove_file(path).ok();
+}
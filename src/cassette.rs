@@ -0,0 +1,460 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING},
+    StatusCode,
+};
+
+use crate::{
+    error::Error,
+    redact::{is_sensitive, redact_headers, redact_json, redact_response_json, REDACTED},
+    transport::{RequestBody, Transport, TransportRequest, TransportResponse},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+    RecordNewEpisodes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedBody {
+    Empty,
+    Json {
+        value: serde_json::Value,
+    },
+    Form {
+        value: serde_json::Value,
+    },
+    // File contents are matched by size and hash so that uploads do not bloat the cassette.
+    Multipart {
+        fields: Vec<(String, String)>,
+        key: String,
+        file_name: String,
+        mime_type: String,
+        size: usize,
+        hash: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: RecordedBody,
+}
+
+impl RecordedRequest {
+    fn from_request(request: &TransportRequest) -> Self {
+        let query = request
+            .url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if is_sensitive(&key) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        let body = match request.body {
+            RequestBody::Empty => RecordedBody::Empty,
+            RequestBody::Json(ref value) => RecordedBody::Json {
+                value: redact_json(value),
+            },
+            RequestBody::Form(ref value) => RecordedBody::Form {
+                value: redact_json(value),
+            },
+            RequestBody::Multipart {
+                ref fields,
+                ref file,
+            } => RecordedBody::Multipart {
+                fields: fields
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = if is_sensitive(key) {
+                            REDACTED.to_string()
+                        } else {
+                            value.clone()
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect(),
+                key: file.key.clone(),
+                file_name: file.file_name.clone(),
+                mime_type: file.mime_type.clone(),
+                size: file.data.len(),
+                hash: format!("{:016x}", fnv1a(&file.data)),
+            },
+        };
+        RecordedRequest {
+            method: request.method.to_string(),
+            path: request.url.path().to_string(),
+            query,
+            headers: redact_headers(&request.headers),
+            body,
+        }
+    }
+
+    // Headers are kept for reference only; they differ between environments.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method
+            && self.path == other.path
+            && self.query == other.query
+            && self.body == other.body
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedPayload {
+    Json(serde_json::Value),
+    Text(String),
+    Base64(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: RecordedPayload,
+}
+
+impl RecordedResponse {
+    fn new(status: StatusCode, headers: &HeaderMap<HeaderValue>, body: &[u8]) -> Self {
+        let is_json = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("application/json"));
+        let body = match (is_json, std::str::from_utf8(body)) {
+            (true, Ok(text)) => match serde_json::from_str(text) {
+                Ok(value) => RecordedPayload::Json(redact_response_json(&value)),
+                Err(_) => RecordedPayload::Text(text.to_string()),
+            },
+            (false, Ok(text)) => RecordedPayload::Text(text.to_string()),
+            (_, Err(_)) => RecordedPayload::Base64(STANDARD.encode(body)),
+        };
+        let headers = redact_headers(headers)
+            .into_iter()
+            .filter(|(name, _)| {
+                name != CONTENT_LENGTH.as_str() && name != TRANSFER_ENCODING.as_str()
+            })
+            .collect();
+        RecordedResponse {
+            status: status.as_u16(),
+            headers,
+            body,
+        }
+    }
+
+    fn to_response(&self) -> Result<TransportResponse, Error> {
+        let status = StatusCode::from_u16(self.status).map_err(|error| {
+            Error::Transport(format!("Invalid recorded status: {}", error).into())
+        })?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        let body = match self.body {
+            RecordedPayload::Json(ref value) => Bytes::from(value.to_string()),
+            RecordedPayload::Text(ref text) => Bytes::from(text.clone()),
+            RecordedPayload::Base64(ref data) => {
+                Bytes::from(STANDARD.decode(data).map_err(|error| {
+                    Error::Transport(format!("Invalid recorded body: {}", error).into())
+                })?)
+            }
+        };
+        Ok(TransportResponse {
+            status,
+            headers,
+            body: body.into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CassetteTransport {
+    path: PathBuf,
+    mode: CassetteMode,
+    inner: Option<Arc<dyn Transport>>,
+    state: Arc<Mutex<CassetteState>>,
+}
+
+impl CassetteTransport {
+    pub fn record<P: AsRef<Path>, T: Transport + 'static>(path: P, inner: T) -> Self {
+        CassetteTransport {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Record,
+            inner: Some(Arc::new(inner)),
+            state: Arc::default(),
+        }
+    }
+
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::load(path.as_ref(), CassetteMode::Replay, None)
+    }
+
+    pub fn record_new_episodes<P: AsRef<Path>, T: Transport + 'static>(
+        path: P,
+        inner: T,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let inner: Arc<dyn Transport> = Arc::new(inner);
+        if path.exists() {
+            Self::load(path, CassetteMode::RecordNewEpisodes, Some(inner))
+        } else {
+            Ok(CassetteTransport {
+                path: path.to_path_buf(),
+                mode: CassetteMode::RecordNewEpisodes,
+                inner: Some(inner),
+                state: Arc::default(),
+            })
+        }
+    }
+
+    fn load(
+        path: &Path,
+        mode: CassetteMode,
+        inner: Option<Arc<dyn Transport>>,
+    ) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        let file: CassetteFile = serde_json::from_str(&content).map_err(|error| {
+            Error::Transport(format!("Invalid cassette {}: {}", path.display(), error).into())
+        })?;
+        let used = vec![false; file.interactions.len()];
+        Ok(CassetteTransport {
+            path: path.to_path_buf(),
+            mode,
+            inner,
+            state: Arc::new(Mutex::new(CassetteState {
+                interactions: file.interactions,
+                used,
+            })),
+        })
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    pub fn unused(&self) -> Vec<Interaction> {
+        let state = self.state.lock().unwrap();
+        state
+            .interactions
+            .iter()
+            .zip(state.used.iter())
+            .filter(|(_, used)| !**used)
+            .map(|(interaction, _)| interaction.clone())
+            .collect()
+    }
+
+    // Identical requests replay in recorded order, so polling loops see the same sequence.
+    fn take_match(&self, request: &RecordedRequest) -> Option<RecordedResponse> {
+        let mut state = self.state.lock().unwrap();
+        let index = state
+            .interactions
+            .iter()
+            .zip(state.used.iter())
+            .position(|(interaction, used)| !used && interaction.request.matches(request))?;
+        state.used[index] = true;
+        Some(state.interactions[index].response.clone())
+    }
+
+    fn append(&self, interaction: Interaction) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        state.interactions.push(interaction);
+        state.used.push(true);
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file).map_err(Error::from)?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let recorded = RecordedRequest::from_request(&request);
+            if self.mode != CassetteMode::Record {
+                if let Some(response) = self.take_match(&recorded) {
+                    return response.to_response();
+                }
+            }
+            let inner = match (self.mode, self.inner.as_ref()) {
+                (CassetteMode::Replay, _) | (_, None) => {
+                    return Err(Error::Transport(
+                        format!(
+                            "No recorded interaction matches {} {} in {}",
+                            recorded.method,
+                            recorded.path,
+                            self.path.display()
+                        )
+                        .into(),
+                    ))
+                }
+                (_, Some(inner)) => inner,
+            };
+            let response = inner.send(request).await?;
+            let status = response.status;
+            let headers = response.headers.clone();
+            let body = reqwest::Response::from(response)
+                .bytes()
+                .await
+                .map_err(Error::from)?;
+            self.append(Interaction {
+                request: recorded,
+                response: RecordedResponse::new(status, &headers, &body),
+            })?;
+            Ok(TransportResponse {
+                status,
+                headers,
+                body: body.into(),
+            })
+        })
+    }
+}
+
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[tokio::test]
+async fn test_cassette_record_and_replay() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let path = std::env::temp_dir().join(format!("appwrite-cassette-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let upstream = MemoryTransport::new();
+    upstream.push_json(
+        StatusCode::CREATED,
+        serde_json::json!({
+            "$id": "t1",
+            "$createdAt": "2023-01-01T00:00:00.000+00:00",
+            "userId": "u1",
+            "secret": "s3cr3t-token",
+            "expire": "2023-01-02T00:00:00.000+00:00",
+        }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(CassetteTransport::record_new_episodes(&path, upstream.clone()).unwrap())
+        .set_key("server-key")
+        .unwrap()
+        .build()
+        .unwrap();
+    let token = AccountsService::create_email_verification(&client, "http://localhost")
+        .await
+        .unwrap();
+    assert_eq!(token.secret, "s3cr3t-token");
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("server-key"));
+    assert!(!content.contains("s3cr3t-token"));
+
+    let cassette = CassetteTransport::replay(&path).unwrap();
+    let client = AppWriteClient::builder("http://ci.example.com/v1", "project")
+        .transport(cassette.clone())
+        .build()
+        .unwrap();
+    let token = AccountsService::create_email_verification(&client, "http://localhost")
+        .await
+        .unwrap();
+    assert_eq!(token.secret, REDACTED);
+    assert!(cassette.unused().is_empty());
+    assert!(HealthService::get_http(&client).await.is_err());
+    assert_eq!(upstream.requests().len(), 1);
+    fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+async fn test_cassette_replays_users_and_sessions() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let path = std::env::temp_dir().join(format!(
+        "appwrite-cassette-accounts-{}.json",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    let fixture = |name: &str| {
+        let path = format!(
+            "{}/tests/fixtures/{}.json",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        serde_json::from_str::<serde_json::Value>(&fs::read_to_string(path).unwrap()).unwrap()
+    };
+    let upstream = MemoryTransport::new();
+    upstream.push_json(StatusCode::OK, fixture("1.2/user"));
+    upstream.push_json(StatusCode::OK, fixture("manual/session_github"));
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(CassetteTransport::record(&path, upstream))
+        .build()
+        .unwrap();
+    let user_id = UserId::new("fixtures".into());
+    let session_id = SessionId::new("63d2603a2ee5a1a5b2c4".into());
+    let recorded_user = UsersService::get_user(&client, &user_id).await.unwrap();
+    let recorded_session = AccountsService::get_session(&client, &session_id)
+        .await
+        .unwrap();
+
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(CassetteTransport::replay(&path).unwrap())
+        .build()
+        .unwrap();
+    let user = UsersService::get_user(&client, &user_id).await.unwrap();
+    assert_eq!(user.password.as_deref(), Some(REDACTED));
+    assert_eq!(user.hash.as_deref(), Some(REDACTED));
+    assert_eq!(user.password_update, recorded_user.password_update);
+    assert!(user.password_update.is_some());
+    let session = AccountsService::get_session(&client, &session_id)
+        .await
+        .unwrap();
+    assert_eq!(session.provider_access_token, REDACTED);
+    assert_eq!(
+        session.provider_access_token_expiry,
+        recorded_session.provider_access_token_expiry
+    );
+    assert!(session.provider_access_token_expiry.is_some());
+    fs::remove_file(path).ok();
+}
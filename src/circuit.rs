@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use smart_default::SmartDefault;
+use url::Url;
+
+use crate::{error::Error, transport::TransportResponse};
+
+#[derive(Debug, Clone, SmartDefault)]
+pub struct CircuitBreaker {
+    #[default = 5]
+    pub failure_threshold: u32,
+    #[default(Duration::from_secs(30))]
+    pub open_duration: Duration,
+    #[default = 1]
+    pub half_open_probes: u32,
+    pub path_prefixes: Vec<String>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold,
+            open_duration,
+            ..Default::default()
+        }
+    }
+
+    pub fn half_open_probes(mut self, half_open_probes: u32) -> Self {
+        self.half_open_probes = half_open_probes;
+        self
+    }
+
+    // Paths under a prefix get their own circuit, e.g. "/v1/databases" so storage keeps working.
+    pub fn path_prefix(mut self, prefix: &str) -> Self {
+        self.path_prefixes.push(prefix.to_string());
+        self
+    }
+
+    fn key(&self, url: &Url) -> String {
+        let mut key = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => String::new(),
+        };
+        let prefix = self
+            .path_prefixes
+            .iter()
+            .filter(|prefix| url.path().starts_with(prefix.as_str()))
+            .max_by_key(|prefix| prefix.len());
+        if let Some(prefix) = prefix {
+            key.push_str(prefix);
+        }
+        key
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { successes: u32, probing: bool },
+}
+
+#[derive(Debug)]
+pub(crate) struct Circuits {
+    config: CircuitBreaker,
+    states: Mutex<HashMap<String, CircuitState>>,
+}
+
+impl Circuits {
+    pub(crate) fn new(config: CircuitBreaker) -> Self {
+        Circuits {
+            config,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn state(&self, url: &Url) -> Option<CircuitState> {
+        self.states
+            .lock()
+            .unwrap()
+            .get(&self.config.key(url))
+            .copied()
+    }
+
+    pub(crate) fn acquire(self: &Arc<Self>, url: &Url) -> Result<CircuitGuard, Error> {
+        let key = self.config.key(url);
+        let now = Instant::now();
+        let mut states = self.states.lock().unwrap();
+        let state = states
+            .entry(key.clone())
+            .or_insert(CircuitState::Closed { failures: 0 });
+        let probe = match *state {
+            CircuitState::Closed { .. } => false,
+            CircuitState::Open { until } if until > now => {
+                return Err(Error::CircuitOpen {
+                    key,
+                    retry_after: Some(until - now),
+                    context: None,
+                })
+            }
+            CircuitState::Open { .. } => {
+                *state = CircuitState::HalfOpen {
+                    successes: 0,
+                    probing: true,
+                };
+                true
+            }
+            CircuitState::HalfOpen { probing: true, .. } => {
+                return Err(Error::CircuitOpen {
+                    key,
+                    retry_after: None,
+                    context: None,
+                })
+            }
+            CircuitState::HalfOpen { successes, .. } => {
+                *state = CircuitState::HalfOpen {
+                    successes,
+                    probing: true,
+                };
+                true
+            }
+        };
+        Ok(CircuitGuard {
+            circuits: self.clone(),
+            key,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, key: &str, success: bool) {
+        let mut states = self.states.lock().unwrap();
+        let state = match states.get_mut(key) {
+            Some(state) => state,
+            None => return,
+        };
+        *state = match (*state, success) {
+            (CircuitState::Closed { .. }, true) => CircuitState::Closed { failures: 0 },
+            (CircuitState::Closed { failures }, false)
+                if failures + 1 < self.config.failure_threshold =>
+            {
+                CircuitState::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (CircuitState::HalfOpen { successes, .. }, true)
+                if successes + 1 < self.config.half_open_probes =>
+            {
+                CircuitState::HalfOpen {
+                    successes: successes + 1,
+                    probing: false,
+                }
+            }
+            (CircuitState::HalfOpen { .. }, true) => CircuitState::Closed { failures: 0 },
+            (CircuitState::Open { until }, _) => CircuitState::Open { until },
+            (_, false) => {
+                warn!(
+                    "Opening circuit for {} for {:?}",
+                    key, self.config.open_duration
+                );
+                CircuitState::Open {
+                    until: Instant::now() + self.config.open_duration,
+                }
+            }
+        };
+    }
+
+    fn release_probe(&self, key: &str) {
+        let mut states = self.states.lock().unwrap();
+        if let Some(CircuitState::HalfOpen { probing, .. }) = states.get_mut(key) {
+            *probing = false;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitGuard {
+    circuits: Arc<Circuits>,
+    key: String,
+    probe: bool,
+    recorded: bool,
+}
+
+impl CircuitGuard {
+    pub(crate) fn record(mut self, result: &Result<TransportResponse, Error>) {
+        let success = match result {
+            Ok(response) => !is_failure_status(response.status),
+            Err(error) => !error.retryable(),
+        };
+        self.circuits.record(&self.key, success);
+        self.recorded = true;
+    }
+}
+
+impl Drop for CircuitGuard {
+    // A cancelled probe must not leave the circuit stuck in half-open.
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.circuits.release_probe(&self.key);
+        }
+    }
+}
+
+fn is_failure_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+#[tokio::test]
+async fn test_circuit_breaker() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .circuit_breaker(
+            CircuitBreaker::new(2, Duration::from_millis(50)).path_prefix("/v1/health/db"),
+        )
+        .build()
+        .unwrap();
+    let unavailable = serde_json::json!({ "message": "", "code": 503, "type": "general_server_error", "version": "1.2.0" });
+    let pass = serde_json::json!({ "ping": 1, "status": "pass" });
+    transport.push_json(StatusCode::SERVICE_UNAVAILABLE, unavailable.clone());
+    transport.push_json(StatusCode::SERVICE_UNAVAILABLE, unavailable);
+    assert!(HealthService::get_db(&client).await.is_err());
+    assert!(HealthService::get_db(&client).await.is_err());
+
+    let error = HealthService::get_db(&client).await.unwrap_err();
+    assert!(matches!(error, Error::CircuitOpen { key, .. } if key == "localhost/v1/health/db"));
+    assert_eq!(transport.requests().len(), 2);
+
+    transport.push_json(StatusCode::OK, pass.clone());
+    HealthService::get_http(&client).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    transport.push_json(StatusCode::OK, pass);
+    HealthService::get_db(&client).await.unwrap();
+    assert_eq!(
+        client.circuit_state("/health/db"),
+        Some(CircuitState::Closed { failures: 0 })
+    );
+}
@@ -1,15 +1,39 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, IntoHeaderName, InvalidHeaderValue},
+    tls, Method, Proxy,
+};
+use url::Url;
+
 use crate::{
     check_response,
-    error::Error,
+    circuit::{CircuitBreaker, CircuitState, Circuits},
+    connection::{ConnectionConfig, OperationTimeouts},
+    endpoint::{is_connection_error, is_endpoint_down, EndpointPool},
+    error::{Error, RequestContext},
+    logging::LogConfig,
+    middleware::{Middleware, Next},
     models::{
         file::{File, InputFile},
         Id,
     },
-};
-
-use reqwest::{
-    header::{HeaderMap, HeaderValue, IntoHeaderName, InvalidHeaderValue},
-    multipart, Method,
+    ratelimit::{EndpointGroup, RateLimit, RateLimitInfo, RateLimiter},
+    response::ResponseMeta,
+    retry::RetryPolicy,
+    services::health::HealthService,
+    session::{SessionCookies, SessionStore},
+    tls::{ClientIdentity, TlsConfig},
+    transport::{
+        MultipartFile, RequestBody, ReqwestTransport, Transport, TransportRequest,
+        TransportResponse,
+    },
+    version::ServerVersion,
 };
 
 #[derive(Debug, Clone)]
@@ -30,7 +54,7 @@ impl Default for AppWriteClientHeader {
         );
         headers.insert(
             "X-Appwrite-Response-Format",
-            HeaderValue::from_static("1.2.0"),
+            ServerVersion::default().header_value(),
         );
         AppWriteClientHeader(headers)
     }
@@ -44,16 +68,26 @@ pub enum RequestData {
     None,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct AppWriteClient {
-    // TODO: Change to private when stable
-    pub client: reqwest::Client,
+    transport: Arc<dyn Transport>,
     project_id: String,
     host_url: String,
-    self_signed: bool,
     chunk_size: usize,
     headers: AppWriteClientHeader,
+    server_version: ServerVersion,
+    retry_policy: Option<RetryPolicy>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    logging: Option<LogConfig>,
+    timeouts: OperationTimeouts,
+    timeout: Option<Duration>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    endpoints: Option<Arc<EndpointPool>>,
+    pinned_endpoint: Option<usize>,
+    circuits: Option<Arc<Circuits>>,
+    session: Arc<Mutex<SessionCookies>>,
+    session_store: Option<Arc<dyn SessionStore>>,
+    session_saves: Arc<Mutex<()>>,
 }
 
 impl AppWriteClient {
@@ -69,6 +103,326 @@ impl AppWriteClient {
         &self.project_id
     }
 
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    pub fn server_version(&self) -> ServerVersion {
+        self.server_version
+    }
+
+    pub fn with_server_version(&self, server_version: ServerVersion) -> AppWriteClient {
+        let mut client = self.clone();
+        client.server_version = server_version;
+        client
+            .headers
+            .add_header("X-Appwrite-Response-Format", server_version.header_value());
+        client
+    }
+
+    // Clones made with `with_*` share the session, so logging in on one authenticates all of them.
+    // Clients holding an API key or a JWT already say who they act as and leave it alone.
+    pub fn session(&self) -> SessionCookies {
+        self.session.lock().unwrap().clone()
+    }
+
+    pub async fn clear_session(&self) {
+        if !self.uses_session() {
+            return;
+        }
+        self.session.lock().unwrap().clear();
+        self.persist_session().await;
+    }
+
+    fn uses_session(&self) -> bool {
+        !self.headers.0.contains_key("x-appwrite-key")
+            && !self.headers.0.contains_key("x-appwrite-jwt")
+    }
+
+    async fn capture_session(&self, headers: &HeaderMap<HeaderValue>) {
+        if !self.uses_session() {
+            return;
+        }
+        let captured = self.session.lock().unwrap().capture(headers);
+        if captured {
+            self.persist_session().await;
+        }
+    }
+
+    // Stores may touch the disk, so they run on the blocking pool. Saves take turns and each
+    // writes the session as it is by then, so the last one always leaves the latest on disk.
+    // The request itself went through, so a store that can't write only costs the next restart.
+    async fn persist_session(&self) {
+        let Some(store) = self.session_store.clone() else {
+            return;
+        };
+        let session = self.session.clone();
+        let saves = self.session_saves.clone();
+        let saved = tokio::task::spawn_blocking(move || {
+            let _turn = saves.lock().unwrap();
+            let session = session.lock().unwrap().clone();
+            store.save(&session)
+        })
+        .await;
+        match saved {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => warn!("Failed to save the session: {}", error),
+            Err(error) => warn!("Failed to save the session: {}", error),
+        }
+    }
+
+    pub fn with_timeout(&self, timeout: Duration) -> AppWriteClient {
+        AppWriteClient {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    // Acting as an end user must not keep the server key around, otherwise the request runs with app scopes.
+    pub fn with_jwt(&self, jwt_token: &str) -> Result<AppWriteClient, InvalidHeaderValue> {
+        let mut client = self.clone();
+        client.headers.0.remove("x-appwrite-key");
+        client
+            .headers
+            .add_header("x-appwrite-jwt", HeaderValue::from_str(jwt_token)?);
+        Ok(client)
+    }
+
+    pub fn with_key(&self, key: &str) -> Result<AppWriteClient, InvalidHeaderValue> {
+        let mut client = self.clone();
+        client.headers.0.remove("x-appwrite-jwt");
+        client
+            .headers
+            .add_header("x-appwrite-key", HeaderValue::from_str(key)?);
+        Ok(client)
+    }
+
+    pub fn with_locale(&self, locale: &str) -> Result<AppWriteClient, InvalidHeaderValue> {
+        let mut client = self.clone();
+        client
+            .headers
+            .add_header("x-appwrite-locale", HeaderValue::from_str(locale)?);
+        Ok(client)
+    }
+
+    pub fn with_header<K: IntoHeaderName, V: Into<HeaderValue>>(
+        &self,
+        key: K,
+        value: V,
+    ) -> AppWriteClient {
+        let mut client = self.clone();
+        client.headers.add_header(key, value);
+        client
+    }
+
+    pub fn circuit_state(&self, path: &str) -> Option<CircuitState> {
+        let url = Url::parse(&format!("{}{}", self.host_url, path)).ok()?;
+        self.circuits.as_ref()?.state(&url)
+    }
+
+    pub fn endpoints(&self) -> Option<&Arc<EndpointPool>> {
+        self.endpoints.as_ref()
+    }
+
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
+    // Pins every request of the returned client to the endpoint currently preferred, e.g. for chunked uploads.
+    pub fn sticky(&self) -> AppWriteClient {
+        match (self.endpoints.as_ref(), self.pinned_endpoint) {
+            (Some(endpoints), None) => AppWriteClient {
+                pinned_endpoint: Some(endpoints.preferred()),
+                ..self.clone()
+            },
+            _ => self.clone(),
+        }
+    }
+
+    pub async fn check_endpoints(&self) {
+        let endpoints = match self.endpoints {
+            Some(ref endpoints) => endpoints,
+            None => return,
+        };
+        for index in 0..endpoints.len() {
+            let client = AppWriteClient {
+                pinned_endpoint: Some(index),
+                ..self.clone()
+            };
+            match HealthService::get_http(&client).await {
+                Err(error) if is_endpoint_down(&error) => endpoints.mark_unhealthy(index),
+                // An open circuit says nothing new about the endpoint.
+                Err(Error::CircuitOpen { .. }) => {}
+                _ => endpoints.mark_healthy(index),
+            }
+        }
+    }
+
+    fn request(&self, method: Method, url: Url, body: RequestBody) -> TransportRequest {
+        let timeout = self.timeout.or(match body {
+            RequestBody::Multipart { .. } => self.timeouts.upload,
+            _ => self.timeouts.for_request(&method, url.path()),
+        });
+        let mut headers = self.headers.0.clone();
+        if self.uses_session() {
+            self.session.lock().unwrap().apply(&mut headers);
+        }
+        TransportRequest {
+            method,
+            url,
+            headers,
+            body,
+            timeout,
+        }
+    }
+
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let endpoints = match self.endpoints {
+            Some(ref endpoints) => endpoints,
+            None => return self.send_to(request).await,
+        };
+        let candidates = match self.pinned_endpoint {
+            Some(index) => vec![index],
+            None => endpoints.candidates(),
+        };
+        let mut last_error = None;
+        for index in candidates {
+            let mut request = request.clone();
+            request.url = endpoints.rewrite(&request.url, index)?;
+            match self.send_to(request).await {
+                Err(error) if is_connection_error(&error) => {
+                    if !matches!(error, Error::CircuitOpen { .. }) {
+                        endpoints.mark_unhealthy(index);
+                    }
+                    if self.pinned_endpoint.is_some() {
+                        return Err(error);
+                    }
+                    last_error = Some(error);
+                }
+                result => {
+                    endpoints.mark_healthy(index);
+                    return result;
+                }
+            }
+        }
+        Err(last_error.unwrap_or(Error::SendFailed))
+    }
+
+    // Middlewares run once per attempt so that signing and metrics see every retry.
+    async fn send_to(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let circuit = match self.circuits {
+            Some(ref circuits) => Some(circuits.acquire(&request.url)?),
+            None => None,
+        };
+        let result = self.send_limited(request).await;
+        if let Some(circuit) = circuit {
+            circuit.record(&result);
+        }
+        result
+    }
+
+    async fn send_limited(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let next = Next::new(&self.middlewares, self.transport.as_ref());
+        let limiter = match self.rate_limiter {
+            Some(ref limiter) => limiter,
+            None => return next.run(request).await,
+        };
+        let permit = limiter.acquire(&request).await;
+        let result = next.run(request).await;
+        if let Ok(ref response) = result {
+            permit.observe(&response.headers);
+        }
+        result
+    }
+
+    pub(crate) async fn dispatch(
+        &self,
+        request: TransportRequest,
+    ) -> Result<reqwest::Response, Error> {
+        #[allow(unused_mut)]
+        let mut request = request;
+        let mut context = RequestContext::new(&request.method, &request.url);
+        #[cfg(feature = "tracing")]
+        crate::telemetry::record_request(&context, &mut request);
+        if let Some(ref logging) = self.logging {
+            logging.log_request(&context, &request);
+        }
+        let started = Instant::now();
+        let result = self.send_with_retry(request).await;
+        let elapsed = started.elapsed();
+        context.client_elapsed = Some(elapsed);
+        let result = match (result, self.logging.as_ref()) {
+            (Ok(mut response), Some(logging)) => logging
+                .log_response(&context, elapsed, &mut response)
+                .await
+                .map(|_| response),
+            (Err(error), Some(logging)) => {
+                logging.log_error(&context, elapsed, &error);
+                Err(error)
+            }
+            (result, None) => result,
+        };
+        #[cfg(feature = "tracing")]
+        match result {
+            Ok(ref response) => crate::telemetry::record_response(response),
+            Err(ref error) => crate::telemetry::record_error(error),
+        }
+        match result {
+            Ok(response) => {
+                context.status = Some(response.status);
+                if response.status.is_success() {
+                    self.capture_session(&response.headers).await;
+                }
+                crate::response::record(|| ResponseMeta {
+                    method: context.method.clone(),
+                    path: context.path.clone(),
+                    status: response.status,
+                    headers: response.headers.clone(),
+                    elapsed,
+                    rate_limit: RateLimitInfo::from_headers(&response.headers),
+                });
+                let mut response = reqwest::Response::from(response);
+                response.extensions_mut().insert(context);
+                Ok(response)
+            }
+            Err(error) => Err(error.with_context(context)),
+        }
+    }
+
+    async fn send_with_retry(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let policy = match self.retry_policy {
+            Some(ref policy) if policy.is_retryable_request(&request) => policy,
+            _ => return self.send(request).await,
+        };
+        let mut attempt = 1;
+        loop {
+            let result = self.send(request.clone()).await;
+            if attempt >= policy.max_attempts {
+                return result;
+            }
+            let delay = match result {
+                Ok(ref response) if policy.is_retryable_status(response.status) => {
+                    policy.delay(attempt, Some(&response.headers))
+                }
+                Err(ref error) if policy.is_retryable_error(error) => policy.delay(attempt, None),
+                _ => None,
+            };
+            let Some(delay) = delay else {
+                return result;
+            };
+            warn!(
+                "Retrying {} {} in {:?} (attempt {}/{})",
+                request.method,
+                request.url.path(),
+                delay,
+                attempt + 1,
+                policy.max_attempts
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
     pub async fn call(
         &self,
         method: Method,
@@ -77,22 +431,16 @@ impl AppWriteClient {
     ) -> Result<reqwest::Response, Error> {
         let url = format!("{}{}", self.host_url, url);
         let url = match content {
-            RequestData::Params(ref params) => {
-                let url = url::Url::parse_with_params(&url, params.iter());
-                match url {
-                    Ok(url) => url.to_string(),
-                    Err(err) => return Err(Error::InvalidUrl(err)),
-                }
-            }
-            _ => url,
-        };
-        let request = self.client.request(method, url);
-        let response = match content {
-            RequestData::Json(json) => request.json(&json).send().await?,
-            RequestData::FormData(json) => request.form(&json).send().await?,
-            _ => request.send().await?,
+            RequestData::Params(ref params) => Url::parse_with_params(&url, params.iter()),
+            _ => Url::parse(&url),
+        }
+        .map_err(Error::InvalidUrl)?;
+        let body = match content {
+            RequestData::Json(json) => RequestBody::Json(json),
+            RequestData::FormData(json) => RequestBody::Form(json),
+            _ => RequestBody::Empty,
         };
-        Ok(response)
+        self.dispatch(self.request(method, url, body)).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -110,115 +458,127 @@ impl AppWriteClient {
     where
         Resp: serde::de::DeserializeOwned + Id,
     {
-        let url = format!("{}{}", self.host_url, url);
+        let path = url;
+        let url = Url::parse(&format!("{}{}", self.host_url, path)).map_err(Error::InvalidUrl)?;
         let filename = input_file.name.clone();
         let mime_type = input_file.mime_type.clone();
         let key = data_param_key.to_string();
-        let data = data.clone();
 
         if input_file.size < self.chunk_size {
-            let mut req = self.client.request(method, url.clone());
+            let mut buf = vec![0; input_file.size];
+            let n = input_file
+                .read_exact(&mut buf)
+                .await
+                .map_err(|error| error.with_context(RequestContext::new(&method, &url)))?;
+            buf.truncate(n);
 
+            let mut req = self.request(
+                method,
+                url,
+                RequestBody::Multipart {
+                    fields: data,
+                    file: MultipartFile {
+                        key,
+                        file_name: filename,
+                        mime_type,
+                        data: buf.into(),
+                    },
+                },
+            );
             if let Some(ref additional_headers) = additional_headers {
                 for (key, value) in additional_headers.iter() {
-                    req = req.header(key, value);
+                    req.headers.insert(key, value.clone());
                 }
             }
-            let fileform = multipart::Part::stream(input_file)
-                .file_name(filename.clone())
-                .mime_str(&mime_type.clone())?;
-            let mut form = multipart::Form::new();
-            for (key, value) in data.iter() {
-                form = form.text(key.clone(), value.clone());
-            }
-            form = form.part(key.clone(), fileform);
 
-            let resp = req.multipart(form).send().await?;
+            let resp = self.dispatch(req).await?;
             let resp = check_response!(Resp: resp);
-            // let resp = resp.json::<Resp>().await?;
             Ok(Some(resp))
         } else {
+            let client = self.sticky();
             let mut offset = 0;
-            let mut counter = 0;
             if let Some(upload_id) = upload_id {
                 if upload_id != "unique()" {
                     // Try to resume upload
-                    let resp = self
+                    let resp = client
                         .call(
                             Method::GET,
-                            &format!("{}/{}", url, upload_id),
+                            &format!("{}/{}", path, upload_id),
                             RequestData::None,
                         )
                         .await?;
                     check_response!(resp);
-                    match resp.json::<File>().await {
-                        Err(_) => {}
-                        Ok(file) => {
-                            counter = file.chunks_uploaded as usize;
-                        }
+                    if let Ok(file) = resp.json::<File>().await {
+                        offset = file.chunks_uploaded as usize * self.chunk_size;
                     }
                 }
             }
-            if counter > 0 {
-                offset = counter * self.chunk_size;
-                input_file.seek(offset).await?;
+            if offset > 0 {
+                input_file
+                    .seek(offset)
+                    .await
+                    .map_err(|error| error.with_context(RequestContext::new(&method, &url)))?;
             }
 
             let mut id: Option<String> = None;
             let mut result: Option<Resp> = None;
             loop {
-                let method = method.clone();
-                let mut req = self.client.request(method, url.clone());
-
                 let mut buf = vec![0; self.chunk_size];
-                let n = input_file.read_exact(&mut buf).await?;
+                let n = input_file
+                    .read_exact(&mut buf)
+                    .await
+                    .map_err(|error| error.with_context(RequestContext::new(&method, &url)))?;
                 buf.truncate(n);
                 if buf.is_empty() {
                     break;
                 }
+
+                let mut fields = Vec::with_capacity(data.len());
+                for (key, value) in data.iter() {
+                    match id {
+                        Some(ref id) if key == id_param_key => {
+                            fields.push((key.clone(), id.clone()))
+                        }
+                        _ => fields.push((key.clone(), value.clone())),
+                    }
+                }
+                let mut req = client.request(
+                    method.clone(),
+                    url.clone(),
+                    RequestBody::Multipart {
+                        fields,
+                        file: MultipartFile {
+                            key: key.clone(),
+                            file_name: filename.clone(),
+                            mime_type: mime_type.clone(),
+                            data: Bytes::from(buf),
+                        },
+                    },
+                );
                 if let Some(ref additional_headers) = additional_headers {
                     for (key, value) in additional_headers.iter() {
-                        req = req.header(key, value);
+                        req.headers.insert(key, value.clone());
                     }
                 }
                 if let Some(ref id) = id {
-                    req = req.header("x-appwrite-id", id.clone())
+                    req.headers
+                        .insert("x-appwrite-id", HeaderValue::from_str(id)?);
                 }
-                req = req.header(
+                req.headers.insert(
                     "content-range",
-                    format!(
+                    HeaderValue::from_str(&format!(
                         "bytes {}-{}/{}",
                         offset,
                         (offset + self.chunk_size - 1).min(input_file.size),
                         input_file.size
-                    ),
+                    ))?,
                 );
-                offset += buf.len();
-
-                let fileform = multipart::Part::stream(buf)
-                    .file_name(filename.clone())
-                    .mime_str(&mime_type.clone())?;
-                let mut form = multipart::Form::new();
-                for (key, value) in data.iter() {
-                    if key == id_param_key {
-                        if let Some(ref id) = id {
-                            form = form.text(key.clone(), id.clone());
-                        } else {
-                            form = form.text(key.clone(), value.clone());
-                        }
-                    } else {
-                        form = form.text(key.clone(), value.clone());
-                    }
-                }
-                form = form.part(key.clone(), fileform);
+                offset += n;
 
-                let resp = req.multipart(form).send().await?;
-                check_response!(resp);
-                let resp = resp.json::<Resp>().await?;
+                let resp = client.dispatch(req).await?;
+                let resp = check_response!(Resp: resp);
                 id = Some(resp.id().clone());
                 result = Some(resp);
-
-                counter += 1;
             }
 
             Ok(result)
@@ -232,6 +592,20 @@ pub struct AppWriteClientBuilder {
     self_signed: bool,
     chunk_size: usize,
     headers: AppWriteClientHeader,
+    server_version: ServerVersion,
+    transport: Option<Arc<dyn Transport>>,
+    retry_policy: Option<RetryPolicy>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    logging: Option<LogConfig>,
+    tls: TlsConfig,
+    connection: ConnectionConfig,
+    timeouts: OperationTimeouts,
+    fallback_endpoints: Vec<String>,
+    endpoint_cooldown: Duration,
+    circuit_breaker: Option<CircuitBreaker>,
+    rate_limit: Option<RateLimit>,
+    group_rate_limits: HashMap<EndpointGroup, RateLimit>,
+    session_store: Option<Arc<dyn SessionStore>>,
 }
 
 impl AppWriteClientBuilder {
@@ -242,6 +616,20 @@ impl AppWriteClientBuilder {
             self_signed: false,
             chunk_size: 5 * 1024 * 1024,
             headers: AppWriteClientHeader::default(),
+            server_version: ServerVersion::default(),
+            transport: None,
+            retry_policy: None,
+            middlewares: Vec::new(),
+            logging: None,
+            tls: TlsConfig::default(),
+            connection: ConnectionConfig::default(),
+            timeouts: OperationTimeouts::default(),
+            fallback_endpoints: Vec::new(),
+            endpoint_cooldown: Duration::from_secs(30),
+            circuit_breaker: None,
+            rate_limit: None,
+            group_rate_limits: HashMap::new(),
+            session_store: None,
         }
     }
     pub fn self_signed(mut self, self_signed: bool) -> Self {
@@ -256,6 +644,119 @@ impl AppWriteClientBuilder {
         self.headers.add_header(key, value);
         self
     }
+    pub fn server_version(mut self, server_version: ServerVersion) -> Self {
+        self.server_version = server_version;
+        self.headers
+            .add_header("X-Appwrite-Response-Format", server_version.header_value());
+        self
+    }
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+    pub fn fallback_endpoint(mut self, host_url: &str) -> Self {
+        self.fallback_endpoints.push(host_url.to_string());
+        self
+    }
+    pub fn endpoint_cooldown(mut self, cooldown: Duration) -> Self {
+        self.endpoint_cooldown = cooldown;
+        self
+    }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+    pub fn group_rate_limit(mut self, group: EndpointGroup, rate_limit: RateLimit) -> Self {
+        self.group_rate_limits.insert(group, rate_limit);
+        self
+    }
+    pub fn session_store<S: SessionStore + 'static>(mut self, session_store: S) -> Self {
+        self.session_store = Some(Arc::new(session_store));
+        self
+    }
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+    pub fn logging(mut self, logging: LogConfig) -> Self {
+        self.logging = Some(logging);
+        self
+    }
+    pub fn add_root_certificate_pem(mut self, pem: &[u8]) -> Self {
+        self.tls.root_certificates.push(pem.to_vec());
+        self
+    }
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.tls.built_in_root_certs = enabled;
+        self
+    }
+    pub fn client_identity_pem(mut self, certificate_pem: &[u8], private_key_pem: &[u8]) -> Self {
+        self.tls.client_identity = Some(ClientIdentity {
+            certificate_pem: certificate_pem.to_vec(),
+            private_key_pem: private_key_pem.to_vec(),
+        });
+        self
+    }
+    pub fn min_tls_version(mut self, version: tls::Version) -> Self {
+        self.tls.min_version = Some(version);
+        self
+    }
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.connection.timeout = Some(timeout);
+        self
+    }
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.connect_timeout = Some(timeout);
+        self
+    }
+    pub fn download_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.download = Some(timeout);
+        self
+    }
+    pub fn upload_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.upload = Some(timeout);
+        self
+    }
+    pub fn execution_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.execution = Some(timeout);
+        self
+    }
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.pool_idle_timeout = Some(timeout);
+        self
+    }
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.connection.pool_max_idle_per_host = Some(max);
+        self
+    }
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.connection.http2_prior_knowledge = true;
+        self
+    }
+    pub fn http1_only(mut self) -> Self {
+        self.connection.http1_only = true;
+        self
+    }
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.connection.user_agent = Some(user_agent.to_string());
+        self
+    }
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.connection.proxies.push(proxy);
+        self
+    }
+    pub fn no_proxy(mut self) -> Self {
+        self.connection.no_proxy = true;
+        self
+    }
 
     pub fn set_jwt_token(mut self, jwt_token: &str) -> Result<Self, InvalidHeaderValue> {
         self.headers
@@ -275,25 +776,113 @@ impl AppWriteClientBuilder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<AppWriteClient, Error> {
-        let mut headers = self.headers.clone();
+    pub fn build(mut self) -> Result<AppWriteClient, Error> {
+        let mut headers = self.headers;
         headers.add_header(
             "X-Appwrite-Project",
             HeaderValue::from_str(&self.project_id)?,
         );
 
-        let client = reqwest::Client::builder()
-            .gzip(true)
-            .default_headers(headers.0)
-            .build()
-            .map_err(Error::FailedToCreateClient)?;
+        // A custom transport brings its own connection, so TLS and connection settings would
+        // silently do nothing.
+        let transport = match self.transport {
+            Some(_) if self.self_signed || !self.tls.is_default() => {
+                return Err(Error::InvalidConfig {
+                    key: "tls".to_string(),
+                    message: "TLS options only apply to the built-in reqwest transport".to_string(),
+                });
+            }
+            Some(_) if !self.connection.is_default() => {
+                return Err(Error::InvalidConfig {
+                    key: "connection".to_string(),
+                    message: "Connection options only apply to the built-in reqwest transport"
+                        .to_string(),
+                });
+            }
+            Some(transport) => transport,
+            None => {
+                self.tls.accept_invalid_certs |= self.self_signed;
+                let client = self
+                    .tls
+                    .apply(self.connection.apply(reqwest::Client::builder().gzip(true)))?
+                    .build()
+                    .map_err(Error::FailedToCreateClient)?;
+                Arc::new(ReqwestTransport::new(client))
+            }
+        };
+        let rate_limiter = if self.rate_limit.is_some() || !self.group_rate_limits.is_empty() {
+            Some(Arc::new(RateLimiter::new(
+                self.rate_limit,
+                self.group_rate_limits,
+            )))
+        } else {
+            None
+        };
+        let session = match self.session_store {
+            Some(ref store) => store.load()?.unwrap_or_default(),
+            None => SessionCookies::default(),
+        };
+        let endpoints = if self.fallback_endpoints.is_empty() {
+            None
+        } else {
+            let urls = std::iter::once(self.host_url.clone()).chain(self.fallback_endpoints);
+            Some(Arc::new(EndpointPool::new(urls, self.endpoint_cooldown)))
+        };
         Ok(AppWriteClient {
-            client,
+            transport,
             project_id: self.project_id,
             host_url: self.host_url,
-            self_signed: self.self_signed,
             chunk_size: self.chunk_size,
-            headers: self.headers,
+            headers,
+            server_version: self.server_version,
+            retry_policy: self.retry_policy,
+            middlewares: self.middlewares,
+            logging: self.logging,
+            timeouts: self.timeouts,
+            rate_limiter,
+            endpoints,
+            pinned_endpoint: None,
+            circuits: self
+                .circuit_breaker
+                .map(|circuit_breaker| Arc::new(Circuits::new(circuit_breaker))),
+            timeout: None,
+            session: Arc::new(Mutex::new(session)),
+            session_store: self.session_store,
+            session_saves: Arc::new(Mutex::new(())),
         })
     }
 }
+
+#[tokio::test]
+async fn test_scoped_credentials() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .set_key("server-key")
+        .unwrap()
+        .build()
+        .unwrap();
+    let user = client
+        .with_jwt("user-jwt")
+        .unwrap()
+        .with_header("x-request-id", HeaderValue::from_static("r1"));
+    for _ in 0..2 {
+        transport.push_json(
+            StatusCode::OK,
+            serde_json::json!({ "ping": 1, "status": "pass" }),
+        );
+    }
+    HealthService::get_http(&user).await.unwrap();
+    HealthService::get_http(&client).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].headers["x-appwrite-jwt"], "user-jwt");
+    assert!(!requests[0].headers.contains_key("x-appwrite-key"));
+    assert_eq!(requests[0].headers["x-request-id"], "r1");
+    assert_eq!(requests[1].headers["x-appwrite-key"], "server-key");
+    assert!(!requests[1].headers.contains_key("x-appwrite-jwt"));
+    assert!(Arc::ptr_eq(client.transport(), user.transport()));
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use crate::{client::AppWriteClientBuilder, error::Error};
+
+/// Required: API endpoint including the version path, e.g. `https://cloud.appwrite.io/v1`.
+pub const ENV_ENDPOINT: &str = "APPWRITE_ENDPOINT";
+/// Required: project id sent as `X-Appwrite-Project`.
+pub const ENV_PROJECT_ID: &str = "APPWRITE_PROJECT_ID";
+/// Server API key, sent as `X-Appwrite-Key`.
+pub const ENV_API_KEY: &str = "APPWRITE_API_KEY";
+/// End-user JWT, sent as `X-Appwrite-JWT`.
+pub const ENV_JWT: &str = "APPWRITE_JWT";
+/// Locale for localized responses, e.g. `en` or `ja`.
+pub const ENV_LOCALE: &str = "APPWRITE_LOCALE";
+/// Accept self-signed certificates: `1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`.
+pub const ENV_SELF_SIGNED: &str = "APPWRITE_SELF_SIGNED";
+/// Upload chunk size in bytes, greater than zero.
+pub const ENV_CHUNK_SIZE: &str = "APPWRITE_CHUNK_SIZE";
+/// Whole-request timeout in seconds.
+pub const ENV_TIMEOUT: &str = "APPWRITE_TIMEOUT";
+/// Connect timeout in seconds.
+pub const ENV_CONNECT_TIMEOUT: &str = "APPWRITE_CONNECT_TIMEOUT";
+/// Server version, e.g. `1.1` or `1.4.3`. Servers newer than 1.2 are still asked for 1.2 responses.
+pub const ENV_SERVER_VERSION: &str = "APPWRITE_SERVER_VERSION";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    pub endpoint: Option<String>,
+    pub project_id: Option<String>,
+    pub api_key: Option<String>,
+    pub jwt: Option<String>,
+    pub locale: Option<String>,
+    pub self_signed: Option<bool>,
+    pub chunk_size: Option<usize>,
+    // Both timeouts are in seconds.
+    pub timeout: Option<u64>,
+    pub connect_timeout: Option<u64>,
+    pub server_version: Option<String>,
+}
+
+impl ClientConfig {
+    /// Reads the client settings from the environment. Each variable maps to the field of the
+    /// same name; unset variables leave the field empty so a config file or the builder can
+    /// fill it in.
+    ///
+    /// | Variable | Field |
+    /// |---|---|
+    /// | [`APPWRITE_ENDPOINT`](ENV_ENDPOINT) | `endpoint` (required) |
+    /// | [`APPWRITE_PROJECT_ID`](ENV_PROJECT_ID) | `project_id` (required) |
+    /// | [`APPWRITE_API_KEY`](ENV_API_KEY) | `api_key` |
+    /// | [`APPWRITE_JWT`](ENV_JWT) | `jwt` |
+    /// | [`APPWRITE_LOCALE`](ENV_LOCALE) | `locale` |
+    /// | [`APPWRITE_SELF_SIGNED`](ENV_SELF_SIGNED) | `self_signed` |
+    /// | [`APPWRITE_CHUNK_SIZE`](ENV_CHUNK_SIZE) | `chunk_size` |
+    /// | [`APPWRITE_TIMEOUT`](ENV_TIMEOUT) | `timeout` |
+    /// | [`APPWRITE_CONNECT_TIMEOUT`](ENV_CONNECT_TIMEOUT) | `connect_timeout` |
+    /// | [`APPWRITE_SERVER_VERSION`](ENV_SERVER_VERSION) | `server_version` |
+    ///
+    /// A value that doesn't parse is an [`Error::InvalidConfig`] naming the variable.
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    pub fn from_vars<F>(var: F) -> Result<Self, Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        Ok(ClientConfig {
+            endpoint: var(ENV_ENDPOINT),
+            project_id: var(ENV_PROJECT_ID),
+            api_key: var(ENV_API_KEY),
+            jwt: var(ENV_JWT),
+            locale: var(ENV_LOCALE),
+            self_signed: parse_var(&var, ENV_SELF_SIGNED, parse_bool)?,
+            chunk_size: parse_var(&var, ENV_CHUNK_SIZE, |value| value.parse().ok())?,
+            timeout: parse_var(&var, ENV_TIMEOUT, |value| value.parse().ok())?,
+            connect_timeout: parse_var(&var, ENV_CONNECT_TIMEOUT, |value| value.parse().ok())?,
+            server_version: var(ENV_SERVER_VERSION),
+        })
+    }
+
+    // Top-level keys are the defaults, `[environments.<name>]` tables override them. Without an
+    // environment only the defaults are loaded.
+    pub fn from_file<P: AsRef<Path>>(path: P, environment: Option<&str>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let invalid = |message: String| Error::InvalidConfig {
+            key: path.display().to_string(),
+            message,
+        };
+        let content = std::fs::read_to_string(path)?;
+        let file: serde_json::Value = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|error| invalid(error.to_string()))?,
+            Some("json") => {
+                serde_json::from_str(&content).map_err(|error| invalid(error.to_string()))?
+            }
+            _ => return Err(invalid("expected a .toml or .json file".to_string())),
+        };
+        // `deny_unknown_fields` doesn't work through `#[serde(flatten)]`, so the environments are
+        // split off by hand and the rest has to be a `ClientConfig` on its own.
+        let mut file = match file {
+            serde_json::Value::Object(file) => file,
+            _ => return Err(invalid("expected a table of settings".to_string())),
+        };
+        let environments = file.remove("environments");
+        let defaults: ClientConfig = serde_json::from_value(serde_json::Value::Object(file))
+            .map_err(|error| invalid(error.to_string()))?;
+        let mut environments: HashMap<String, ClientConfig> = match environments {
+            Some(environments) => serde_json::from_value(environments)
+                .map_err(|error| invalid(format!("environments: {}", error)))?,
+            None => HashMap::new(),
+        };
+        let environment = match environment {
+            Some(environment) => environment,
+            None => return Ok(defaults),
+        };
+        match environments.remove(environment) {
+            Some(overrides) => Ok(defaults.merge(overrides)),
+            None => Err(Error::InvalidConfig {
+                key: format!("environments.{}", environment),
+                message: format!("environment not found in {}", path.display()),
+            }),
+        }
+    }
+
+    pub fn merge(self, other: ClientConfig) -> Self {
+        ClientConfig {
+            endpoint: other.endpoint.or(self.endpoint),
+            project_id: other.project_id.or(self.project_id),
+            api_key: other.api_key.or(self.api_key),
+            jwt: other.jwt.or(self.jwt),
+            locale: other.locale.or(self.locale),
+            self_signed: other.self_signed.or(self.self_signed),
+            chunk_size: other.chunk_size.or(self.chunk_size),
+            timeout: other.timeout.or(self.timeout),
+            connect_timeout: other.connect_timeout.or(self.connect_timeout),
+            server_version: other.server_version.or(self.server_version),
+        }
+    }
+
+    pub fn into_builder(self) -> Result<AppWriteClientBuilder, Error> {
+        let endpoint = self
+            .endpoint
+            .ok_or_else(|| Error::MissingConfig("endpoint".to_string()))?;
+        url::Url::parse(&endpoint).map_err(|error| Error::InvalidConfig {
+            key: "endpoint".to_string(),
+            message: error.to_string(),
+        })?;
+        let project_id = self
+            .project_id
+            .ok_or_else(|| Error::MissingConfig("project_id".to_string()))?;
+
+        let mut builder = AppWriteClientBuilder::new(&endpoint, &project_id);
+        if let Some(ref key) = self.api_key {
+            builder = builder.set_key(key).map_err(|error| Error::InvalidConfig {
+                key: "api_key".to_string(),
+                message: error.to_string(),
+            })?;
+        }
+        if let Some(ref jwt) = self.jwt {
+            builder = builder
+                .set_jwt_token(jwt)
+                .map_err(|error| Error::InvalidConfig {
+                    key: "jwt".to_string(),
+                    message: error.to_string(),
+                })?;
+        }
+        if let Some(ref locale) = self.locale {
+            builder = builder
+                .set_locale(locale)
+                .map_err(|error| Error::InvalidConfig {
+                    key: "locale".to_string(),
+                    message: error.to_string(),
+                })?;
+        }
+        if let Some(self_signed) = self.self_signed {
+            builder = builder.self_signed(self_signed);
+        }
+        if let Some(chunk_size) = self.chunk_size {
+            if chunk_size == 0 {
+                return Err(Error::InvalidConfig {
+                    key: "chunk_size".to_string(),
+                    message: "must be greater than zero".to_string(),
+                });
+            }
+            builder = builder.chunk_size(chunk_size);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(Duration::from_secs(timeout));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_secs(timeout));
+        }
+        if let Some(ref version) = self.server_version {
+            let version = version.parse().map_err(|_| Error::InvalidConfig {
+                key: "server_version".to_string(),
+                message: format!("could not parse {:?}", version),
+            })?;
+            builder = builder.server_version(version);
+        }
+        Ok(builder)
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_var<F, T, P>(var: &F, key: &str, parse: P) -> Result<Option<T>, Error>
+where
+    F: Fn(&str) -> Option<String>,
+    P: Fn(&str) -> Option<T>,
+{
+    match var(key) {
+        None => Ok(None),
+        Some(value) => match parse(value.trim()) {
+            Some(value) => Ok(Some(value)),
+            None => Err(Error::InvalidConfig {
+                key: key.to_string(),
+                message: format!("could not parse {:?}", value),
+            }),
+        },
+    }
+}
+
+impl AppWriteClientBuilder {
+    /// A builder from the variables listed on [`ClientConfig::from_env`].
+    pub fn from_env() -> Result<Self, Error> {
+        ClientConfig::from_env()?.into_builder()
+    }
+
+    pub fn from_config_file<P: AsRef<Path>>(
+        path: P,
+        environment: Option<&str>,
+    ) -> Result<Self, Error> {
+        ClientConfig::from_file(path, environment)?.into_builder()
+    }
+}
+
+#[test]
+fn test_config_from_vars() {
+    let vars = maplit::hashmap! {
+        ENV_ENDPOINT => "http://localhost/v1",
+        ENV_PROJECT_ID => "project",
+        ENV_SELF_SIGNED => "true",
+        ENV_CHUNK_SIZE => "1048576",
+    };
+    let config = ClientConfig::from_vars(|key| vars.get(key).map(|v| v.to_string())).unwrap();
+    assert_eq!(config.self_signed, Some(true));
+    assert_eq!(config.chunk_size, Some(1048576));
+    assert!(config.into_builder().is_ok());
+
+    let vars = maplit::hashmap! { ENV_TIMEOUT => "soon" };
+    let error = ClientConfig::from_vars(|key| vars.get(key).map(|v| v.to_string())).unwrap_err();
+    assert!(matches!(error, Error::InvalidConfig { ref key, .. } if key == ENV_TIMEOUT));
+
+    let error = ClientConfig::default().into_builder().err().unwrap();
+    assert!(matches!(error, Error::MissingConfig(ref key) if key == "endpoint"));
+}
+
+#[test]
+fn test_config_from_file() {
+    let path = std::env::temp_dir().join(format!("appwrite-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+project_id = "shared"
+chunk_size = 1024
+
+[environments.dev]
+endpoint = "http://localhost/v1"
+server_version = "1.1"
+
+[environments.prod]
+endpoint = "https://cloud.appwrite.io/v1"
+project_id = "production"
+"#,
+    )
+    .unwrap();
+    let dev = ClientConfig::from_file(&path, Some("dev")).unwrap();
+    assert_eq!(dev.project_id.as_deref(), Some("shared"));
+    assert_eq!(dev.server_version.as_deref(), Some("1.1"));
+    let prod = ClientConfig::from_file(&path, Some("prod")).unwrap();
+    assert_eq!(prod.project_id.as_deref(), Some("production"));
+    assert_eq!(prod.chunk_size, Some(1024));
+    assert!(ClientConfig::from_file(&path, Some("staging")).is_err());
+    let defaults = ClientConfig::from_file(&path, None).unwrap();
+    assert_eq!(defaults.project_id.as_deref(), Some("shared"));
+    assert_eq!(defaults.endpoint, None);
+
+    // Typos are rejected at the top level just like inside an environment.
+    for content in [
+        "endpiont = \"http://localhost/v1\"\n",
+        "[environments.dev]\nendpiont = \"http://localhost/v1\"\n",
+    ] {
+        std::fs::write(&path, content).unwrap();
+        let error = ClientConfig::from_file(&path, None).unwrap_err();
+        assert!(
+            matches!(error, Error::InvalidConfig { ref message, .. } if message.contains("endpiont")),
+            "{:?}",
+            error
+        );
+    }
+    std::fs::remove_file(path).ok();
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use reqwest::{ClientBuilder, Method, Proxy};
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionConfig {
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub http2_prior_knowledge: bool,
+    pub http1_only: bool,
+    pub user_agent: Option<String>,
+    pub proxies: Vec<Proxy>,
+    pub no_proxy: bool,
+}
+
+impl ConnectionConfig {
+    pub fn is_default(&self) -> bool {
+        self.timeout.is_none()
+            && self.connect_timeout.is_none()
+            && self.pool_idle_timeout.is_none()
+            && self.pool_max_idle_per_host.is_none()
+            && !self.http2_prior_knowledge
+            && !self.http1_only
+            && self.user_agent.is_none()
+            && self.proxies.is_empty()
+            && !self.no_proxy
+    }
+
+    pub(crate) fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if self.http1_only {
+            builder = builder.http1_only();
+        }
+        if let Some(ref user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if self.no_proxy {
+            builder = builder.no_proxy();
+        }
+        for proxy in self.proxies.iter() {
+            builder = builder.proxy(proxy.clone());
+        }
+        builder
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OperationTimeouts {
+    pub download: Option<Duration>,
+    pub upload: Option<Duration>,
+    pub execution: Option<Duration>,
+}
+
+impl OperationTimeouts {
+    pub(crate) fn for_request(&self, method: &Method, path: &str) -> Option<Duration> {
+        let segments = path.trim_end_matches('/').split('/').collect::<Vec<_>>();
+        match (method, segments.as_slice()) {
+            (&Method::GET, [.., "files", _, "download" | "view" | "preview"]) => self.download,
+            (&Method::POST, [.., "functions", _, "executions"]) => self.execution,
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_operation_timeouts() {
+    let timeouts = OperationTimeouts {
+        download: Some(Duration::from_secs(600)),
+        upload: None,
+        execution: Some(Duration::from_secs(900)),
+    };
+    assert_eq!(
+        timeouts.for_request(&Method::GET, "/storage/buckets/b/files/f/download"),
+        Some(Duration::from_secs(600))
+    );
+    assert_eq!(
+        timeouts.for_request(&Method::POST, "/functions/f/executions"),
+        Some(Duration::from_secs(900))
+    );
+    assert_eq!(
+        timeouts.for_request(&Method::GET, "/functions/f/executions"),
+        None
+    );
+}
+
+#[test]
+fn test_connection_options_need_reqwest() {
+    use crate::{client::AppWriteClient, error::Error, transport::MemoryTransport};
+
+    let builder = || {
+        AppWriteClient::builder("http://localhost/v1", "project").transport(MemoryTransport::new())
+    };
+    let rejected = [
+        builder().timeout(Duration::from_secs(1)),
+        builder().connect_timeout(Duration::from_secs(1)),
+        builder().pool_idle_timeout(Duration::from_secs(1)),
+        builder().pool_max_idle_per_host(1),
+        builder().http2_prior_knowledge(),
+        builder().http1_only(),
+        builder().user_agent("agent"),
+        builder().proxy(Proxy::all("http://proxy.local:8080").unwrap()),
+        builder().no_proxy(),
+    ];
+    for builder in rejected {
+        assert!(matches!(
+            builder.build(),
+            Err(Error::InvalidConfig { ref key, .. }) if key == "connection"
+        ));
+    }
+    // Per-operation timeouts travel with each request, so any transport can honor them.
+    assert!(builder()
+        .download_timeout(Duration::from_secs(1))
+        .build()
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_request_timeout_override() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    transport.push_response(StatusCode::OK, Default::default(), "data");
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .download_timeout(Duration::from_secs(600))
+        .build()
+        .unwrap();
+    StoragesService::get_file_download(
+        &client,
+        &BucketId::new("b".into()),
+        &FileId::new("f".into()),
+    )
+    .await
+    .unwrap();
+    HealthService::get_http(&client.with_timeout(Duration::from_secs(1)))
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].timeout, Some(Duration::from_secs(600)));
+    assert_eq!(requests[1].timeout, Some(Duration::from_secs(1)));
+}
@@ -0,0 +1,207 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use url::Url;
+
+use crate::error::Error;
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    parsed: Option<Url>,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+#[derive(Debug)]
+pub struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    cooldown: Duration,
+}
+
+impl EndpointPool {
+    pub fn new<I: IntoIterator<Item = String>>(urls: I, cooldown: Duration) -> Self {
+        EndpointPool {
+            endpoints: urls
+                .into_iter()
+                .map(|url| Endpoint {
+                    parsed: Url::parse(&url).ok(),
+                    url,
+                    unhealthy_until: Mutex::new(None),
+                })
+                .collect(),
+            cooldown,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn url(&self, index: usize) -> &str {
+        &self.endpoints[index].url
+    }
+
+    pub fn is_healthy(&self, index: usize) -> bool {
+        let until = *self.endpoints[index].unhealthy_until.lock().unwrap();
+        until.is_none_or(|until| until <= Instant::now())
+    }
+
+    pub fn mark_healthy(&self, index: usize) {
+        *self.endpoints[index].unhealthy_until.lock().unwrap() = None;
+    }
+
+    pub fn mark_unhealthy(&self, index: usize) {
+        warn!(
+            "Endpoint {} is unreachable, skipping it for {:?}",
+            self.endpoints[index].url, self.cooldown
+        );
+        *self.endpoints[index].unhealthy_until.lock().unwrap() =
+            Some(Instant::now() + self.cooldown);
+    }
+
+    // Healthy endpoints keep their configured order; the rest follow so a request is never refused outright.
+    pub(crate) fn candidates(&self) -> Vec<usize> {
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) =
+            (0..self.endpoints.len()).partition(|index| self.is_healthy(*index));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    pub(crate) fn preferred(&self) -> usize {
+        self.candidates().first().copied().unwrap_or(0)
+    }
+
+    // Compares parsed URLs so `https://Example.com:443/v1/` and `https://example.com/v1` match.
+    pub(crate) fn rewrite(&self, url: &Url, index: usize) -> Result<Url, Error> {
+        let primary = match self.endpoints[0].parsed {
+            Some(ref primary) if index != 0 && url.origin() == primary.origin() => primary,
+            _ => return Ok(url.clone()),
+        };
+        let base = primary.path().trim_end_matches('/');
+        let rest = match url.path().strip_prefix(base) {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+            _ => return Ok(url.clone()),
+        };
+        let endpoint = &self.endpoints[index];
+        let mut rewritten = match endpoint.parsed {
+            Some(ref parsed) => parsed.clone(),
+            None => return Url::parse(&endpoint.url).map_err(Error::InvalidUrl),
+        };
+        let path = format!("{}{}", rewritten.path().trim_end_matches('/'), rest);
+        rewritten.set_path(&path);
+        rewritten.set_query(url.query());
+        rewritten.set_fragment(url.fragment());
+        Ok(rewritten)
+    }
+}
+
+pub(crate) fn is_connection_error(error: &Error) -> bool {
+    match error {
+        Error::Request(error) => error.is_connect(),
+        Error::Transport(_) | Error::CircuitOpen { .. } => true,
+        _ => false,
+    }
+}
+
+// Whether a health check says the endpoint itself is down. A 401 from a key without `health.read`
+// or a 429 still comes from a working server.
+pub(crate) fn is_endpoint_down(error: &Error) -> bool {
+    matches!(error, Error::Request(_) | Error::Transport(_))
+        || error
+            .status()
+            .is_some_and(|status| status.is_server_error())
+}
+
+#[test]
+fn test_endpoint_candidates() {
+    let pool = EndpointPool::new(
+        vec![
+            "https://eu.example.com/v1".to_string(),
+            "https://us.example.com/v1".to_string(),
+        ],
+        Duration::from_secs(30),
+    );
+    assert_eq!(pool.candidates(), vec![0, 1]);
+    pool.mark_unhealthy(0);
+    assert_eq!(pool.candidates(), vec![1, 0]);
+    let url = Url::parse("https://eu.example.com/v1/health?x=1").unwrap();
+    assert_eq!(
+        pool.rewrite(&url, 1).unwrap().as_str(),
+        "https://us.example.com/v1/health?x=1"
+    );
+
+    let pool = EndpointPool::new(
+        vec![
+            "https://EU.example.com:443/v1/".to_string(),
+            "https://us.example.com/v1".to_string(),
+        ],
+        Duration::from_secs(30),
+    );
+    assert_eq!(
+        pool.rewrite(&url, 1).unwrap().as_str(),
+        "https://us.example.com/v1/health?x=1"
+    );
+    let other = Url::parse("https://eu.example.com/v10/health").unwrap();
+    assert_eq!(pool.rewrite(&other, 1).unwrap(), other);
+}
+
+#[tokio::test]
+async fn test_endpoint_failover() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("https://eu.example.com/v1", "project")
+        .fallback_endpoint("https://us.example.com/v1")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    transport.push_error("connection refused");
+    for _ in 0..2 {
+        transport.push_json(
+            StatusCode::OK,
+            serde_json::json!({ "ping": 1, "status": "pass" }),
+        );
+    }
+    HealthService::get_http(&client).await.unwrap();
+    HealthService::get_http(&client.sticky()).await.unwrap();
+
+    let hosts = transport
+        .requests()
+        .iter()
+        .map(|request| request.url.host_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hosts,
+        vec!["eu.example.com", "us.example.com", "us.example.com"]
+    );
+    assert!(!client.endpoints().unwrap().is_healthy(0));
+
+    // Only outages count against an endpoint, not a key that lacks the `health.read` scope.
+    let unauthorized = serde_json::json!({
+        "message": "User (role: guests) missing scope (health.read)",
+        "code": 401,
+        "type": "general_unauthorized_scope",
+        "version": "1.2.0"
+    });
+    transport.push_json(StatusCode::UNAUTHORIZED, unauthorized.clone());
+    transport.push_json(StatusCode::UNAUTHORIZED, unauthorized);
+    client.check_endpoints().await;
+    assert!(client.endpoints().unwrap().is_healthy(0));
+    assert!(client.endpoints().unwrap().is_healthy(1));
+
+    transport.push_json(
+        StatusCode::SERVICE_UNAVAILABLE,
+        serde_json::json!({ "message": "", "code": 503, "type": "general_server_error", "version": "1.2.0" }),
+    );
+    transport.push_error("connection refused");
+    client.check_endpoints().await;
+    assert!(!client.endpoints().unwrap().is_healthy(0));
+    assert!(!client.endpoints().unwrap().is_healthy(1));
+}
@@ -1,59 +1,536 @@
-use crate::models::ErrorResponse;
-use reqwest::header::InvalidHeaderValue;
+use std::{fmt, time::Duration};
+
+use crate::{models::ErrorResponse, redact::redact_url};
+use reqwest::{header::InvalidHeaderValue, Method, StatusCode};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize_enum_str, Deserialize_enum_str)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    GeneralMock,
+    GeneralAccessForbidden,
+    GeneralUnknownOrigin,
+    GeneralServiceDisabled,
+    GeneralUnauthorizedScope,
+    GeneralRateLimitExceeded,
+    GeneralSmtpDisabled,
+    GeneralPhoneDisabled,
+    GeneralArgumentInvalid,
+    GeneralQueryLimitExceeded,
+    GeneralQueryInvalid,
+    GeneralCursorNotFound,
+    GeneralServerError,
+    GeneralRouteNotFound,
+    GeneralProtocolUnsupported,
+    GeneralUsageDisabled,
+
+    UserCountExceeded,
+    UserJwtInvalid,
+    UserAlreadyExists,
+    UserBlocked,
+    UserInvalidToken,
+    UserPasswordResetRequired,
+    UserEmailNotWhitelisted,
+    UserInvalidCode,
+    UserIpNotWhitelisted,
+    UserInvalidCredentials,
+    UserAnonymousConsoleProhibited,
+    UserSessionAlreadyExists,
+    UserNotFound,
+    UserEmailAlreadyExists,
+    UserPasswordMismatch,
+    UserSessionNotFound,
+    UserUnauthorized,
+    UserAuthMethodUnsupported,
+    UserPhoneAlreadyExists,
+    UserPhoneNotFound,
+    UserMissingId,
+
+    TeamNotFound,
+    TeamInviteAlreadyExists,
+    TeamInviteNotFound,
+    TeamInvalidSecret,
+    TeamMembershipMismatch,
+    TeamInviteMismatch,
+    TeamAlreadyExists,
+
+    MembershipNotFound,
+    MembershipAlreadyConfirmed,
+
+    AvatarSetNotFound,
+    AvatarNotFound,
+    AvatarImageNotFound,
+    AvatarRemoteUrlFailed,
+    AvatarIconNotFound,
+
+    StorageFileAlreadyExists,
+    StorageFileNotFound,
+    StorageDeviceNotFound,
+    StorageFileEmpty,
+    StorageFileTypeUnsupported,
+    StorageInvalidFileSize,
+    StorageInvalidFile,
+    StorageBucketAlreadyExists,
+    StorageBucketNotFound,
+    StorageInvalidContentRange,
+    StorageInvalidRange,
+
+    FunctionNotFound,
+    FunctionRuntimeUnsupported,
+
+    BuildNotFound,
+    BuildNotReady,
+    BuildInProgress,
+
+    DeploymentNotFound,
+
+    ExecutionNotFound,
+
+    DatabaseNotFound,
+    DatabaseAlreadyExists,
+
+    CollectionNotFound,
+    CollectionAlreadyExists,
+    CollectionLimitExceeded,
+
+    DocumentNotFound,
+    DocumentInvalidStructure,
+    DocumentMissingPayload,
+    DocumentAlreadyExists,
+    DocumentUpdateConflict,
+
+    AttributeNotFound,
+    AttributeUnknown,
+    AttributeNotAvailable,
+    AttributeFormatUnsupported,
+    AttributeDefaultUnsupported,
+    AttributeAlreadyExists,
+    AttributeLimitExceeded,
+    AttributeValueInvalid,
+
+    IndexNotFound,
+    IndexLimitExceeded,
+    IndexAlreadyExists,
+    IndexInvalid,
+
+    ProjectNotFound,
+    ProjectUnknown,
+    ProjectProviderDisabled,
+    ProjectProviderUnsupported,
+    ProjectInvalidSuccessUrl,
+    ProjectInvalidFailureUrl,
+    ProjectReservedProject,
+    ProjectKeyExpired,
+
+    RouterHostNotFound,
+    RouterDomainNotActive,
+
+    VariableNotFound,
+    VariableAlreadyExists,
+
+    WebhookNotFound,
+
+    KeyNotFound,
+
+    PlatformNotFound,
+
+    DomainNotFound,
+    DomainAlreadyExists,
+    DomainVerificationFailed,
+
+    #[serde(other)]
+    Unknown(String),
+}
+
+impl ApiErrorKind {
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ApiErrorKind::GeneralRouteNotFound | ApiErrorKind::RouterHostNotFound => false,
+            kind => kind.to_string().ends_with("_not_found"),
+        }
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorKind::DocumentUpdateConflict | ApiErrorKind::MembershipAlreadyConfirmed
+        ) || self.to_string().ends_with("_already_exists")
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorKind::GeneralAccessForbidden
+                | ApiErrorKind::GeneralUnauthorizedScope
+                | ApiErrorKind::UserJwtInvalid
+                | ApiErrorKind::UserBlocked
+                | ApiErrorKind::UserInvalidToken
+                | ApiErrorKind::UserInvalidCredentials
+                | ApiErrorKind::UserUnauthorized
+                | ApiErrorKind::UserSessionNotFound
+                | ApiErrorKind::ProjectKeyExpired
+        )
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, ApiErrorKind::GeneralRateLimitExceeded)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub method: Method,
+    pub path: String,
+    pub status: Option<StatusCode>,
+    // Measured by the client around all attempts, so it includes retries and backoff.
+    pub client_elapsed: Option<Duration>,
+}
+
+impl RequestContext {
+    pub fn new(method: &Method, url: &Url) -> Self {
+        RequestContext {
+            method: method.clone(),
+            path: redact_url(url),
+            status: None,
+            client_elapsed: None,
+        }
+    }
+
+    pub fn from_response(response: &reqwest::Response) -> Option<Self> {
+        response.extensions().get::<RequestContext>().cloned()
+    }
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        match (self.status, self.client_elapsed) {
+            (Some(status), Some(elapsed)) => {
+                write!(f, " ({}, {}ms)", status, elapsed.as_millis())
+            }
+            (Some(status), None) => write!(f, " ({})", status),
+            (None, Some(elapsed)) => write!(f, " ({}ms)", elapsed.as_millis()),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+// Request errors carry the request they came from next to the underlying error. It derefs to that
+// error, so arms like `Error::Request(error) => error.is_timeout()` read as before.
+#[derive(Debug)]
+pub struct WithContext<E> {
+    pub error: E,
+    pub context: Option<Box<RequestContext>>,
+}
+
+impl<E> WithContext<E> {
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+
+    fn prefix(&self) -> Prefix<'_> {
+        Prefix(self.context.as_deref())
+    }
+}
+
+impl<E> From<E> for WithContext<E> {
+    fn from(error: E) -> Self {
+        WithContext {
+            error,
+            context: None,
+        }
+    }
+}
+
+impl<E> std::ops::Deref for WithContext<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for WithContext<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for WithContext<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+struct Prefix<'a>(Option<&'a RequestContext>);
+
+impl fmt::Display for Prefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(context) => write!(f, "{}: ", context),
+            None => Ok(()),
+        }
+    }
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Failed to send")]
     SendFailed,
-    #[error("{0}")]
-    IOError(#[from] std::io::Error),
+    #[error("{}{}", .0.prefix(), .0.error)]
+    IOError(#[source] WithContext<std::io::Error>),
     #[error("Error while creating client: {0}")]
     FailedToCreateClient(#[source] reqwest::Error),
-    #[error("Error while sending request: {0}")]
-    Request(#[source] reqwest::Error),
-    #[error("Error while parsing response: {0}")]
-    ParseResponse(#[source] serde_json::Error),
+    #[error("{}Error while sending request: {}", .0.prefix(), .0.error)]
+    Request(#[source] WithContext<reqwest::Error>),
+    #[error("{}Error while parsing response: {}", .0.prefix(), .0.error)]
+    ParseResponse(#[source] WithContext<serde_json::Error>),
     #[error("Invalid Header Value: {0}")]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
-    #[error("Invalid Response Body: {0}")]
-    InvalidResponseBody(reqwest::Error),
+    #[error("{}Invalid Response Body: {}", .0.prefix(), .0.error)]
+    InvalidResponseBody(WithContext<reqwest::Error>),
+    #[error("{}Transport error: {}", .0.prefix(), .0.error)]
+    Transport(WithContext<String>),
+    #[error("{}Circuit open for {key}", Prefix(.context.as_deref()))]
+    CircuitOpen {
+        key: String,
+        retry_after: Option<Duration>,
+        context: Option<Box<RequestContext>>,
+    },
+    #[error("No response was received to take metadata from")]
+    MissingResponseMeta,
+    #[error("Missing configuration value: {0}")]
+    MissingConfig(String),
+    #[error("Invalid configuration value for {key}: {message}")]
+    InvalidConfig { key: String, message: String },
+    #[error("Invalid server version: {0}")]
+    InvalidServerVersion(String),
     #[error("Invalid Url: {0}")]
     InvalidUrl(url::ParseError),
-    #[error("{{0.message}}")]
+    // API errors keep their context in `ErrorResponse::context`.
+    #[error("{0}")]
     ApiError(ErrorResponse),
 }
 
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::IOError(error.into())
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
         error!("{:?}", error);
-        Error::Request(error)
+        Error::Request(error.into())
     }
 }
 
 impl From<serde_json::Error> for Error {
     fn from(error: serde_json::Error) -> Self {
         error!("Deserialize Error: {:?}", error);
-        Error::ParseResponse(error)
+        Error::ParseResponse(error.into())
     }
 }
 
 impl Error {
     pub async fn from_response(response: reqwest::Response) -> Self {
+        let context = RequestContext::from_response(&response);
         let body = response.text().await;
         let body = match body {
             Ok(body) => body,
             Err(error) => {
                 error!("Error while parsing response: {:?}", error);
-                return Error::InvalidResponseBody(error);
+                return Error::InvalidResponseBody(error.into()).with_context(context);
             }
         };
         let error = match serde_json::from_str::<ErrorResponse>(&body) {
             Ok(error) => error,
             Err(error) => {
                 error!("Error while parsing response: {:?}", error);
-                return Error::ParseResponse(error);
+                return Error::ParseResponse(error.into()).with_context(context);
             }
         };
-        Error::ApiError(error)
+        Error::ApiError(error).with_context(context)
+    }
+
+    // Only errors that come out of a request can hold one; the first context given is kept.
+    pub fn with_context<C: Into<Option<RequestContext>>>(mut self, context: C) -> Self {
+        if let (Some(slot), Some(context)) = (self.context_slot(), context.into()) {
+            if slot.is_none() {
+                *slot = Some(Box::new(context));
+            }
+        }
+        self
+    }
+
+    fn context_slot(&mut self) -> Option<&mut Option<Box<RequestContext>>> {
+        match self {
+            Error::IOError(error) => Some(&mut error.context),
+            Error::Request(error) | Error::InvalidResponseBody(error) => Some(&mut error.context),
+            Error::ParseResponse(error) => Some(&mut error.context),
+            Error::Transport(error) => Some(&mut error.context),
+            Error::CircuitOpen { context, .. } => Some(context),
+            Error::ApiError(error) => Some(&mut error.context),
+            _ => None,
+        }
+    }
+
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Error::IOError(error) => error.context.as_deref(),
+            Error::Request(error) | Error::InvalidResponseBody(error) => error.context.as_deref(),
+            Error::ParseResponse(error) => error.context.as_deref(),
+            Error::Transport(error) => error.context.as_deref(),
+            Error::CircuitOpen { context, .. } => context.as_deref(),
+            Error::ApiError(error) => error.context.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn method(&self) -> Option<&Method> {
+        self.context().map(|context| &context.method)
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.context().map(|context| context.path.as_str())
     }
+
+    pub fn client_elapsed(&self) -> Option<Duration> {
+        self.context().and_then(|context| context.client_elapsed)
+    }
+}
+
+impl Error {
+    pub fn api_error(&self) -> Option<&ErrorResponse> {
+        match self {
+            Error::ApiError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> Option<ApiErrorKind> {
+        self.api_error().map(ErrorResponse::kind)
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        if let Some(status) = self.context().and_then(|context| context.status) {
+            return Some(status);
+        }
+        match self {
+            Error::ApiError(error) => StatusCode::from_u16(error.code).ok(),
+            Error::Request(error) | Error::InvalidResponseBody(error) => error.status(),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+            || self.kind().is_some_and(|kind| kind.is_not_found())
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT)
+            || self.kind().is_some_and(|kind| kind.is_conflict())
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.status() == Some(StatusCode::UNAUTHORIZED)
+            || self.kind().is_some_and(|kind| kind.is_unauthorized())
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+            || self.kind().is_some_and(|kind| kind.is_rate_limited())
+    }
+
+    pub fn retryable(&self) -> bool {
+        match self {
+            Error::Request(error) => error.is_connect() || error.is_timeout() || error.is_request(),
+            Error::Transport(_) => true,
+            Error::ApiError(_) => {
+                self.is_rate_limited()
+                    || matches!(
+                        self.status(),
+                        Some(
+                            StatusCode::BAD_GATEWAY
+                                | StatusCode::SERVICE_UNAVAILABLE
+                                | StatusCode::GATEWAY_TIMEOUT
+                        )
+                    )
+            }
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn test_api_error_kind() {
+    let error = Error::ApiError(ErrorResponse {
+        code: 404,
+        message: "Document with the requested ID could not be found.".to_string(),
+        error_type: "document_not_found".to_string(),
+        version: "1.2.0".to_string(),
+        context: None,
+    });
+    assert_eq!(error.kind(), Some(ApiErrorKind::DocumentNotFound));
+    assert!(error.is_not_found());
+    assert!(!error.retryable());
+    assert_eq!(
+        error.to_string(),
+        "Document with the requested ID could not be found. (document_not_found)"
+    );
+
+    let kind = "user_already_exists".parse::<ApiErrorKind>().unwrap();
+    assert!(kind.is_conflict());
+    let kind = "some_future_error".parse::<ApiErrorKind>().unwrap();
+    assert_eq!(kind, ApiErrorKind::Unknown("some_future_error".to_string()));
+}
+
+#[tokio::test]
+async fn test_error_context() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({
+            "message": "User with the requested ID could not be found.",
+            "code": 404,
+            "type": "user_not_found",
+            "version": "1.2.0"
+        }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let error = UsersService::get_user(&client, &UserId::new("u1".into()))
+        .await
+        .unwrap_err();
+    assert!(error.is_not_found());
+    assert!(matches!(error, Error::ApiError(_)));
+    assert_eq!(error.method(), Some(&Method::GET));
+    assert_eq!(error.path(), Some("/v1/users/u1"));
+    assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+    assert!(error
+        .to_string()
+        .starts_with("GET /v1/users/u1 (404 Not Found, "));
+    assert!(error.client_elapsed().is_some());
+
+    transport.push_error("connection reset");
+    let error = HealthService::get_http(&client).await.unwrap_err();
+    assert!(matches!(error, Error::Transport(_)));
+    assert_eq!(error.path(), Some("/v1/health"));
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "GET /v1/health ({}ms): Transport error: connection reset",
+            error.client_elapsed().unwrap().as_millis()
+        )
+    );
+    let Error::Transport(error) = error else {
+        unreachable!()
+    };
+    assert_eq!(error.into_inner(), "connection reset");
 }
@@ -10,11 +10,30 @@ extern crate derive_more;
 #[macro_use]
 extern crate param_macro;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cassette;
+pub mod circuit;
 pub mod client;
+pub mod config;
+pub mod connection;
+pub mod endpoint;
 pub mod error;
+pub mod logging;
 pub mod macros;
+pub mod middleware;
 pub mod models;
+pub mod ratelimit;
+mod redact;
+pub mod response;
+pub mod retry;
 pub mod services;
+pub mod session;
+#[cfg(feature = "tracing")]
+mod telemetry;
+pub mod tls;
+pub mod transport;
+pub mod version;
 
 pub use attr_macro::AppWriteModel;
 use prelude::DeploymentId;
@@ -23,12 +42,26 @@ use serde::Deserialize;
 pub mod prelude {
     pub use super::AppWriteModel;
     pub use crate::client::AppWriteClient;
-    pub use crate::error::Error;
+    pub use crate::error::{ApiErrorKind, Error};
+    pub use crate::logging::LogConfig;
+    pub use crate::ratelimit::{EndpointGroup, RateLimit};
+    pub use crate::response::{Response, ResponseExt};
+    pub use crate::retry::RetryPolicy;
     pub use crate::models::prelude::*;
     pub use crate::services::{
         accounts::*, avatars::*, databases::*, functions::*, health::*, locales::*, storages::*,
         teams::*, users::*, CursorDirection, Order, SearchPayload, SearchQueryPayload,
     };
+    pub use crate::services::generated::prelude::*;
+    pub use crate::version::ServerVersion;
+}
+
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
 }
 
 pub(crate) fn empty_deploy_as_none<'de, D>(
@@ -37,10 +70,6 @@ pub(crate) fn empty_deploy_as_none<'de, D>(
 where
     D: serde::Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
-    if s.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(DeploymentId::new(s)))
-    }
+    let s = Option::<String>::deserialize(deserializer)?;
+    Ok(s.filter(|s| !s.is_empty()).map(DeploymentId::new))
 }
@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+use log::Level;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::{
+    error::{Error, RequestContext},
+    redact::{is_sensitive, redact_headers, redact_json, REDACTED},
+    transport::{RequestBody, TransportRequest, TransportResponse},
+};
+
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub level: Level,
+    pub headers: bool,
+    pub bodies: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig::new(Level::Debug)
+    }
+}
+
+impl LogConfig {
+    pub fn new(level: Level) -> Self {
+        LogConfig {
+            level,
+            headers: false,
+            bodies: false,
+        }
+    }
+
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn bodies(mut self, bodies: bool) -> Self {
+        self.bodies = bodies;
+        self
+    }
+
+    pub(crate) fn log_request(&self, context: &RequestContext, request: &TransportRequest) {
+        log!(self.level, "--> {} {}", context.method, context.path);
+        if self.headers {
+            for (name, value) in redact_headers(&request.headers) {
+                log!(self.level, "    {}: {}", name, value);
+            }
+        }
+        if self.bodies {
+            match request.body {
+                RequestBody::Empty => {}
+                RequestBody::Json(ref json) | RequestBody::Form(ref json) => {
+                    log!(self.level, "    {}", redact_json(json));
+                }
+                RequestBody::Multipart {
+                    ref fields,
+                    ref file,
+                } => {
+                    for (key, value) in fields {
+                        let value = if is_sensitive(key) { REDACTED } else { value };
+                        log!(self.level, "    {}={}", key, value);
+                    }
+                    log!(
+                        self.level,
+                        "    {}=<{} {} bytes>",
+                        file.key,
+                        file.mime_type,
+                        file.data.len()
+                    );
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn log_response(
+        &self,
+        context: &RequestContext,
+        elapsed: Duration,
+        response: &mut TransportResponse,
+    ) -> Result<(), Error> {
+        log!(
+            self.level,
+            "<-- {} {} {} ({}ms)",
+            response.status,
+            context.method,
+            context.path,
+            elapsed.as_millis()
+        );
+        if self.headers {
+            for (name, value) in redact_headers(&response.headers) {
+                log!(self.level, "    {}: {}", name, value);
+            }
+        }
+        let is_json = response
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("application/json"));
+        if self.bodies && is_json {
+            // Only JSON bodies are buffered so that file downloads keep streaming.
+            let body = std::mem::replace(&mut response.body, reqwest::Body::from(""));
+            let bytes = reqwest::Response::from(http::Response::new(body))
+                .bytes()
+                .await?;
+            match serde_json::from_slice::<serde_json::Value>(&bytes) {
+                Ok(json) => log!(self.level, "    {}", redact_json(&json)),
+                Err(_) => log!(self.level, "    <{} bytes>", bytes.len()),
+            }
+            response.body = bytes.into();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn log_error(&self, context: &RequestContext, elapsed: Duration, error: &Error) {
+        log!(
+            self.level,
+            "<-- {} {} failed ({}ms): {}",
+            context.method,
+            context.path,
+            elapsed.as_millis(),
+            error
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_logging_keeps_body() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 3, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport)
+        .logging(LogConfig::new(Level::Info).headers(true).bodies(true))
+        .build()
+        .unwrap();
+    let health = HealthService::get_http(&client).await.unwrap();
+    assert_eq!(health.ping, 3);
+}
+
+#[tokio::test]
+async fn test_logging_redacts() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::{
+        header::{HeaderMap, HeaderValue, SET_COOKIE},
+        StatusCode,
+    };
+    use std::{
+        sync::Mutex,
+        thread::{self, ThreadId},
+    };
+
+    // The logger is global, so only lines from this test's thread are kept.
+    struct Capture(Mutex<Vec<(ThreadId, String)>>);
+
+    impl log::Log for Capture {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let line = record.args().to_string();
+            self.0.lock().unwrap().push((thread::current().id(), line));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE: Capture = Capture(Mutex::new(vec![]));
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let transport = MemoryTransport::new();
+    let mut session: serde_json::Value =
+        serde_json::from_str(include_str!("../tests/fixtures/1.2/session_email.json")).unwrap();
+    session["secret"] = "session-secret".into();
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=cookie-secret; path=/; httponly"),
+    );
+    transport.push_response(StatusCode::CREATED, headers, session.to_string());
+    for _ in 0..3 {
+        transport.push_json(
+            StatusCode::OK,
+            serde_json::json!({ "ping": 3, "status": "pass" }),
+        );
+    }
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport)
+        .logging(LogConfig::new(Level::Info).headers(true).bodies(true))
+        .build()
+        .unwrap();
+    AccountsService::create_email_session(&client, "reader@example.com", "password-secret")
+        .await
+        .unwrap();
+    HealthService::get_http(&client).await.unwrap();
+    HealthService::get_http(&client.with_key("key-secret").unwrap())
+        .await
+        .unwrap();
+    HealthService::get_http(&client.with_jwt("jwt-secret").unwrap())
+        .await
+        .unwrap();
+
+    let id = thread::current().id();
+    let lines = CAPTURE
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(thread, _)| *thread == id)
+        .map(|(_, line)| line.clone())
+        .collect::<Vec<_>>();
+    let log = lines.join("\n");
+    for secret in [
+        "password-secret",
+        "session-secret",
+        "cookie-secret",
+        "key-secret",
+        "jwt-secret",
+    ] {
+        assert!(!log.contains(secret), "{} leaked into:\n{}", secret, log);
+    }
+    for line in [
+        "    set-cookie: [REDACTED]",
+        "    cookie: [REDACTED]",
+        "    x-appwrite-key: [REDACTED]",
+        "    x-appwrite-jwt: [REDACTED]",
+        "    x-appwrite-project: project",
+        r#"    {"email":"reader@example.com","password":"[REDACTED]"}"#,
+        r#"    {"ping":3,"status":"pass"}"#,
+    ] {
+        assert!(
+            lines.iter().any(|logged| logged == line),
+            "{} missing from:\n{}",
+            line,
+            log
+        );
+    }
+    assert!(lines
+        .iter()
+        .any(|line| line.contains(r#""secret":"[REDACTED]""#)
+            && line.contains(r#""provider":"email""#)));
+    assert!(lines.contains(&"--> POST /v1/account/sessions/email".to_string()));
+}
@@ -2,11 +2,15 @@
 macro_rules! check_response {
     ($ty:ty: $response:expr) => {{
         if !$response.status().is_success() {
-            return Err(Error::from_response($response).await);
+            return Err($crate::error::Error::from_response($response).await);
         }
-        let data = $response.text().await?;
-        println!("{}", data);
-        serde_json::from_str::<$ty>(&data)?
+        let context = $crate::error::RequestContext::from_response(&$response);
+        let data = $response
+            .text()
+            .await
+            .map_err(|error| $crate::error::Error::from(error).with_context(context.clone()))?;
+        serde_json::from_str::<$ty>(&data)
+            .map_err(|error| $crate::error::Error::from(error).with_context(context))?
     }};
     ($response:expr) => {{
         if !$response.status().is_success() {
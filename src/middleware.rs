@@ -0,0 +1,109 @@
+use std::{fmt, sync::Arc};
+
+use futures_util::future::BoxFuture;
+
+use crate::{
+    error::Error,
+    transport::{Transport, TransportRequest, TransportResponse},
+};
+
+pub trait Middleware: fmt::Debug + Send + Sync {
+    fn handle<'a>(
+        &'a self,
+        request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>>;
+}
+
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middlewares: &'a [Arc<dyn Middleware>],
+        transport: &'a dyn Transport,
+    ) -> Self {
+        Next {
+            middlewares,
+            transport,
+        }
+    }
+
+    pub fn run(self, request: TransportRequest) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    middlewares: rest,
+                    transport: self.transport,
+                },
+            ),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_chain() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::{header::HeaderValue, StatusCode};
+
+    #[derive(Debug)]
+    struct Tenant;
+
+    impl Middleware for Tenant {
+        fn handle<'a>(
+            &'a self,
+            mut request: TransportRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+            request
+                .headers
+                .insert("x-tenant", HeaderValue::from_static("acme"));
+            next.run(request)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Maintenance;
+
+    impl Middleware for Maintenance {
+        fn handle<'a>(
+            &'a self,
+            request: TransportRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+            Box::pin(async move {
+                if request.url.path().ends_with("/health/db") {
+                    return Ok(TransportResponse::json(
+                        StatusCode::OK,
+                        &serde_json::json!({ "ping": 0, "status": "fail" }),
+                    ));
+                }
+                next.run(request).await
+            })
+        }
+    }
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .middleware(Tenant)
+        .middleware(Maintenance)
+        .build()
+        .unwrap();
+    HealthService::get_http(&client).await.unwrap();
+    let health = HealthService::get_db(&client).await.unwrap();
+    assert_eq!(health.ping, 0);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].headers["x-tenant"], "acme");
+}
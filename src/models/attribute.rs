@@ -1,4 +1,4 @@
-use super::{collection::CollectionId, database::DatabaseId, DataStatus};
+use super::{collection::CollectionId, database::DatabaseId, DataStatus, ListKey};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -6,10 +6,12 @@ pub enum Attribute {
     Boolean(AttributeBoolean),
     Integer(AttributeInteger),
     Double(AttributeDouble),
+    #[serde(rename = "datetime")]
+    DateTime(AttributeDateTime),
     String(AttributeStringLike),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(tag = "format", rename_all = "camelCase")]
 pub enum AttributeStringLike {
     Email(AttributeEmail),
@@ -20,6 +22,48 @@ pub enum AttributeStringLike {
     String(AttributeString),
 }
 
+// Appwrite leaves `format` out for plain string attributes, so the tag can't be required.
+impl<'de> serde::Deserialize<'de> for AttributeStringLike {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(tag = "format", rename_all = "camelCase")]
+        enum Tagged {
+            Email(AttributeEmail),
+            DateTime(AttributeDateTime),
+            Enum(AttributeEnum),
+            Url(AttributeUrl),
+            Ip(AttributeIp),
+            String(AttributeString),
+        }
+
+        let mut value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+        if let Some(object) = value.as_object_mut() {
+            object.entry("format").or_insert_with(|| "string".into());
+        }
+        Ok(
+            match <Tagged as serde::Deserialize>::deserialize(value)
+                .map_err(serde::de::Error::custom)?
+            {
+                Tagged::Email(attribute) => AttributeStringLike::Email(attribute),
+                Tagged::DateTime(attribute) => AttributeStringLike::DateTime(attribute),
+                Tagged::Enum(attribute) => AttributeStringLike::Enum(attribute),
+                Tagged::Url(attribute) => AttributeStringLike::Url(attribute),
+                Tagged::Ip(attribute) => AttributeStringLike::Ip(attribute),
+                Tagged::String(attribute) => AttributeStringLike::String(attribute),
+            },
+        )
+    }
+}
+
+impl ListKey for Attribute {
+    fn list_key() -> &'static str {
+        "attributes"
+    }
+}
+
 impl Attribute {
     pub fn new_boolean(
         key: &str,
@@ -112,13 +156,13 @@ impl Attribute {
         default: Option<String>,
         is_array: Option<bool>,
     ) -> Self {
-        Attribute::String(AttributeStringLike::DateTime(AttributeDateTime {
+        Attribute::DateTime(AttributeDateTime {
             key: key.to_string(),
             required,
             default_value: default.unwrap_or_default(),
             is_array: is_array.unwrap_or_default(),
             status: DataStatus::default(),
-        }))
+        })
     }
 
     pub fn new_url(
@@ -188,6 +232,7 @@ impl Attribute {
             Attribute::Boolean(_) => format!("{}/boolean", base),
             Attribute::Integer(_) => format!("{}/integer", base),
             Attribute::Double(_) => format!("{}/float", base),
+            Attribute::DateTime(_) => format!("{}/datetime", base),
             Attribute::String(AttributeStringLike::Email(_)) => format!("{}/email", base),
             Attribute::String(AttributeStringLike::DateTime(_)) => format!("{}/datetime", base),
             Attribute::String(AttributeStringLike::Enum(_)) => format!("{}/enum", base),
@@ -209,7 +254,7 @@ pub struct AttributeBoolean {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: bool,
 }
 
@@ -246,7 +291,7 @@ pub struct AttributeEmail {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
 }
 
@@ -257,7 +302,7 @@ pub struct AttributeDateTime {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
 }
 
@@ -268,7 +313,7 @@ pub struct AttributeEnum {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
     pub elements: Vec<String>,
 }
@@ -280,7 +325,7 @@ pub struct AttributeUrl {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
 }
 
@@ -291,7 +336,7 @@ pub struct AttributeIp {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
 }
 
@@ -302,7 +347,7 @@ pub struct AttributeString {
     pub required: bool,
     #[serde(rename = "array")]
     pub is_array: bool,
-    #[serde(rename = "default")]
+    #[serde(rename = "default", deserialize_with = "crate::null_as_default")]
     pub default_value: String,
     pub size: usize,
 }
@@ -39,7 +39,6 @@ pub struct Bucket {
     pub enabled: bool,
     pub maximum_file_size: FileSize,
     pub allowed_file_extensions: Vec<String>,
-    #[serde(skip_serializing_if = "Compression::is_none")]
     pub compression: Compression,
     pub encryption: bool,
     pub antivirus: bool,
@@ -53,8 +53,12 @@ pub struct Deployment {
     pub build_id: String, // TODO: Change string to BuildId
     pub activate: bool,
     pub status: DeploymentStatus,
+    // 1.4 merged both streams into `buildLogs`, which isn't modeled.
+    #[serde(default)]
     pub build_stdout: String,
+    #[serde(default)]
     pub build_stderr: String,
+    pub build_time: u64,
 }
 
 impl ListKey for Deployment {
@@ -23,7 +23,8 @@ pub struct Document<T> {
     pub id: DocumentId,
     #[serde(rename = "$collectionId")]
     pub collection_id: CollectionId,
-    #[serde(rename = "$databaseId")]
+    // 1.1 servers leave this out, DatabasesService fills it in from the request.
+    #[serde(rename = "$databaseId", default)]
     pub database_id: DatabaseId,
     #[serde(rename = "$createdAt")]
     pub created_at: TimeStamp,
@@ -41,7 +42,15 @@ impl<T> ListKey for Document<T> {
     }
 }
 
-// TODO: v1.2では何故か前仕様の返り値のため、database_id実装後にコメントアウトを外す
+impl<T> Document<T> {
+    pub(crate) fn or_database_id(mut self, database_id: &DatabaseId) -> Self {
+        if self.database_id.0.is_empty() {
+            self.database_id = database_id.clone();
+        }
+        self
+    }
+}
+
 impl<T> Document<T>
 where
     T: for<'de> serde::de::Deserialize<'de> + serde::Serialize + Clone,
@@ -1,4 +1,3 @@
-
 use super::{function::FunctionId, permission::Permission, Id, ListKey, TimeStamp};
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -36,15 +35,21 @@ pub struct Execution {
     pub created_at: TimeStamp,
     #[serde(rename = "$updatedAt")]
     pub updated_at: TimeStamp,
-    #[serde(rename = "$read")]
-    pub read_perms: Vec<Permission>,
+    #[serde(rename = "$permissions")]
+    pub permissions: Vec<Permission>,
     pub function_id: FunctionId,
     pub trigger: ExecutionTrigger,
     pub status: ExecutionStatus,
+    // 1.4 renamed these four.
+    #[serde(alias = "responseStatusCode")]
     pub status_code: u16,
+    #[serde(alias = "responseBody")]
     pub response: String,
+    #[serde(alias = "logs")]
+    pub stdout: String,
+    #[serde(alias = "errors")]
     pub stderr: String,
-    pub time: f64,
+    pub duration: f64,
 }
 
 impl ListKey for Execution {
@@ -129,6 +129,26 @@ impl InputFile {
         })
     }
 
+    pub fn from_path_blocking<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let meta = file.metadata()?;
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => "Unknown".to_string(),
+        };
+        let mime_type = mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string();
+        Ok(InputFile {
+            name,
+            size: meta.len() as usize,
+            mime_type,
+            cursor: 0,
+            ty: InputFileType::File(tokio::fs::File::from_std(file)),
+        })
+    }
+
     pub async fn from_bytes(
         bytes: Vec<u8>,
         name: Option<String>,
@@ -1,4 +1,3 @@
-
 use crate::{empty_deploy_as_none, prelude::*};
 
 use super::{Id, ListKey};
@@ -34,20 +33,21 @@ pub struct Function {
     pub updated_at: TimeStamp,
     pub execute: Vec<String>,
     pub name: String,
-    pub status: FunctionStatus,
+    pub enabled: bool,
     pub runtime: ExecutionRuntime,
     #[serde(deserialize_with = "empty_deploy_as_none")]
     pub deployment: Option<DeploymentId>,
     // TODO: v1.2では環境変数が未設定の場合には空の配列が返ってくるが、
     // 設定済みの場合にはJSONオブジェクトが返ってくる。
     // 仕様変更に応じて修正する。
-    pub vars: serde_json::Value,
+    pub vars: Vec<Variable>,
     pub events: Vec<String>,
     pub schedule: String,
-    #[serde()]
-    pub schedule_next: TimeStamp,
-    #[serde()]
-    pub schedule_previous: TimeStamp,
+    // Empty for functions without a schedule, dropped altogether in 1.4.
+    #[serde(with = "super::timestamp::optional", default)]
+    pub schedule_next: Option<TimeStamp>,
+    #[serde(with = "super::timestamp::optional", default)]
+    pub schedule_previous: Option<TimeStamp>,
     pub timeout: u64,
 }
 
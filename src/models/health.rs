@@ -1,5 +1,3 @@
-
-use super::TimeStamp;
 use crate::prelude::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,10 +17,8 @@ pub struct Health {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HealthTime {
-    #[serde()]
-    pub remote_time: TimeStamp,
-    #[serde()]
-    pub local_time: TimeStamp,
+    pub remote_time: i64,
+    pub local_time: i64,
     pub diff: i64,
 }
 
@@ -47,6 +43,12 @@ pub struct HealthAntivirus {
     pub status: HealthAntivirusStatus,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthVersion {
+    pub version: String,
+}
+
 impl Health {
     pub async fn http(client: &AppWriteClient) -> Result<Self, crate::error::Error> {
         HealthService::get_http(client).await
@@ -60,6 +62,10 @@ impl Health {
         HealthService::get_cache(client).await
     }
 
+    pub async fn version(client: &AppWriteClient) -> Result<HealthVersion, crate::error::Error> {
+        HealthService::get_version(client).await
+    }
+
     pub async fn time(client: &AppWriteClient) -> Result<HealthTime, crate::error::Error> {
         HealthService::get_time(client).await
     }
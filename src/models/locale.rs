@@ -123,6 +123,7 @@ impl ListKey for Currency {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Language {
+    #[serde(with = "iso639_1")]
     pub code: ISOLanguage,
     pub name: String,
     pub native_name: String,
@@ -133,3 +134,28 @@ impl ListKey for Language {
         "languages"
     }
 }
+
+// Appwrite sends two-letter ISO 639-1 codes, while `iso639_enum` only reads ISO 639-3 ones.
+mod iso639_1 {
+    use iso639_enum::IsoCompat;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::ISOLanguage;
+
+    pub fn serialize<S>(code: &ISOLanguage, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(code.iso639_1().unwrap_or_else(|| code.iso639_3()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ISOLanguage, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        ISOLanguage::from_iso639_1(&code)
+            .or_else(|_| ISOLanguage::from_iso639_3(&code))
+            .map_err(|_| serde::de::Error::custom(format!("unknown language code: {}", code)))
+    }
+}
@@ -1,6 +1,5 @@
 use std::net::IpAddr;
 
-
 use super::{user::UserId, ListKey, TimeStamp};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,9 +25,8 @@ pub struct Log {
     pub device_name: String,
     pub device_brand: String,
     pub device_model: String,
-    pub cuntry_code: String,
-    pub cuntry_name: String,
-    pub current: bool,
+    pub country_code: String,
+    pub country_name: String,
 }
 
 impl ListKey for Log {
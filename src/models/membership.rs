@@ -3,7 +3,7 @@ use crate::prelude::*;
 
 use super::{user::UserId, ListKey, TimeStamp};
 
-#[derive(Debug, Display, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Display, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct MembershipId(pub String);
 
 impl MembershipId {
@@ -28,8 +28,8 @@ pub struct Membership {
     pub team_name: String,
     #[serde()]
     pub invited: TimeStamp,
-    #[serde()]
-    pub joined: TimeStamp,
+    #[serde(with = "super::timestamp::optional", default)]
+    pub joined: Option<TimeStamp>,
     pub confirm: bool,
     pub roles: Vec<String>,
 }
@@ -30,7 +30,7 @@ pub enum Role {
     User((UserId, Option<UserStatus>)),
     Users(Option<UserStatus>),
     Guests,
-    Team((TeamId, Option<Box<Role>>)),
+    Team((TeamId, Option<String>)),
     Member(MembershipId),
 }
 
@@ -62,10 +62,9 @@ impl FromStr for Role {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.splitn(2, ':');
-        let role = parts.next().ok_or(())?;
         // remove double quotes
-        let role = role.trim_matches('"');
+        let mut parts = s.trim_matches('"').splitn(2, ':');
+        let role = parts.next().ok_or(())?;
         match role {
             "any" => Ok(Role::Any),
             "user" => {
@@ -109,9 +108,8 @@ impl FromStr for Role {
                 let role = parts.next();
                 match role {
                     Some(role) => {
-                        let role = role.parse().map_err(|_| ())?;
                         let team_id = TeamId::new(team_id.into());
-                        Ok(Role::Team((team_id, Some(Box::new(role)))))
+                        Ok(Role::Team((team_id, Some(role.to_string()))))
                     }
                     None => {
                         let team_id = TeamId::new(team_id.into());
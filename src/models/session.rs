@@ -1,6 +1,5 @@
 use std::net::IpAddr;
 
-
 use super::{user::UserId, ListKey, TimeStamp};
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -25,7 +24,8 @@ pub struct Session {
     pub provider: String,
     pub provider_uid: String,
     pub provider_access_token: String,
-    pub provider_access_token_expiry: TimeStamp,
+    #[serde(with = "super::timestamp::optional", default)]
+    pub provider_access_token_expiry: Option<TimeStamp>,
     pub provider_refresh_token: String,
     pub ip: IpAddr,
     pub os_code: String,
@@ -40,8 +40,8 @@ pub struct Session {
     pub device_name: String,
     pub device_brand: String,
     pub device_model: String,
-    pub cuntry_code: String,
-    pub cuntry_name: String,
+    pub country_code: String,
+    pub country_name: String,
     pub current: bool,
 }
 
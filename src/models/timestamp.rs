@@ -0,0 +1,412 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+#[cfg(not(feature = "chrono"))]
+use time::{
+    format_description::well_known::Rfc3339, macros::format_description, OffsetDateTime,
+    PrimitiveDateTime, UtcOffset,
+};
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+compile_error!("TimeStamp needs either the `chrono` or the `time` feature");
+
+// Always kept in UTC so values compare and serialize the same on every machine. Backed by chrono
+// unless only the `time` feature is enabled.
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TimeStamp(pub DateTime<Utc>);
+
+#[cfg(not(feature = "chrono"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeStamp(pub OffsetDateTime);
+
+#[cfg(feature = "chrono")]
+pub type ParseError = chrono::ParseError;
+
+#[cfg(not(feature = "chrono"))]
+pub type ParseError = time::error::Parse;
+
+#[cfg(feature = "chrono")]
+impl TimeStamp {
+    pub fn now() -> Self {
+        TimeStamp(Utc::now())
+    }
+
+    pub fn from_unix(seconds: i64) -> Option<Self> {
+        Utc.timestamp_opt(seconds, 0).single().map(TimeStamp)
+    }
+
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    pub fn to_utc(&self) -> DateTime<Utc> {
+        self.0
+    }
+
+    pub fn to_local(&self) -> DateTime<Local> {
+        self.0.with_timezone(&Local)
+    }
+
+    pub fn to_offset(&self, offset: FixedOffset) -> DateTime<FixedOffset> {
+        self.0.with_timezone(&offset)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Deref for TimeStamp {
+    type Target = DateTime<Utc>;
+
+    fn deref(&self) -> &DateTime<Utc> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for TimeStamp {
+    fn from(datetime: DateTime<Tz>) -> Self {
+        TimeStamp(datetime.with_timezone(&Utc))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<TimeStamp> for DateTime<Utc> {
+    fn from(timestamp: TimeStamp) -> Self {
+        timestamp.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<TimeStamp> for DateTime<Local> {
+    fn from(timestamp: TimeStamp) -> Self {
+        timestamp.to_local()
+    }
+}
+
+// Same shape Appwrite sends, e.g. `2023-01-26T11:03:23.436+00:00`.
+#[cfg(feature = "chrono")]
+impl fmt::Display for TimeStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::Millis, false))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromStr for TimeStamp {
+    type Err = ParseError;
+
+    // Besides RFC 3339, datetime attributes and pre-1.0 servers hand out naive UTC times.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        DateTime::parse_from_rfc3339(s)
+            .map(TimeStamp::from)
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").map(naive))
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").map(naive))
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn naive(datetime: NaiveDateTime) -> TimeStamp {
+    TimeStamp(Utc.from_utc_datetime(&datetime))
+}
+
+#[cfg(all(feature = "chrono", feature = "time"))]
+impl From<time::OffsetDateTime> for TimeStamp {
+    fn from(datetime: time::OffsetDateTime) -> Self {
+        // `time` only covers years up to 9999, which chrono always can represent.
+        Utc.timestamp_opt(datetime.unix_timestamp(), datetime.nanosecond())
+            .single()
+            .map(TimeStamp)
+            .expect("time::OffsetDateTime is within chrono's range")
+    }
+}
+
+#[cfg(all(feature = "chrono", feature = "time"))]
+impl TryFrom<TimeStamp> for time::OffsetDateTime {
+    type Error = time::error::ComponentRange;
+
+    fn try_from(timestamp: TimeStamp) -> Result<Self, Self::Error> {
+        let nanos = i128::from(timestamp.0.timestamp()) * 1_000_000_000
+            + i128::from(timestamp.0.timestamp_subsec_nanos());
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl TimeStamp {
+    pub fn now() -> Self {
+        TimeStamp(OffsetDateTime::now_utc())
+    }
+
+    pub fn from_unix(seconds: i64) -> Option<Self> {
+        OffsetDateTime::from_unix_timestamp(seconds)
+            .ok()
+            .map(TimeStamp)
+    }
+
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0.unix_timestamp()
+    }
+
+    pub fn to_utc(&self) -> OffsetDateTime {
+        self.0
+    }
+
+    // Stays in UTC where the local offset can't be read safely, e.g. in multi-threaded programs
+    // on some unixes.
+    pub fn to_local(&self) -> OffsetDateTime {
+        self.0
+            .to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC))
+    }
+
+    pub fn to_offset(&self, offset: UtcOffset) -> OffsetDateTime {
+        self.0.to_offset(offset)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl Default for TimeStamp {
+    fn default() -> Self {
+        TimeStamp(OffsetDateTime::UNIX_EPOCH)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl Deref for TimeStamp {
+    type Target = OffsetDateTime;
+
+    fn deref(&self) -> &OffsetDateTime {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl From<OffsetDateTime> for TimeStamp {
+    fn from(datetime: OffsetDateTime) -> Self {
+        TimeStamp(datetime.to_offset(UtcOffset::UTC))
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl From<TimeStamp> for OffsetDateTime {
+    fn from(timestamp: TimeStamp) -> Self {
+        timestamp.0
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl fmt::Display for TimeStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = format_description!(
+            "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]+00:00"
+        );
+        f.write_str(&self.0.format(&format).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl FromStr for TimeStamp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let space = format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]]"
+        );
+        let t = format_description!(
+            "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+        );
+        OffsetDateTime::parse(s, &Rfc3339)
+            .map(TimeStamp::from)
+            .or_else(|_| {
+                PrimitiveDateTime::parse(s, &space).map(|datetime| TimeStamp(datetime.assume_utc()))
+            })
+            .or_else(|_| {
+                PrimitiveDateTime::parse(s, &t).map(|datetime| TimeStamp(datetime.assume_utc()))
+            })
+    }
+}
+
+impl serde::Serialize for TimeStamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TimeStamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(TimeStampVisitor { optional: false })?
+            .ok_or_else(|| serde::de::Error::custom("expected a datetime, got an empty value"))
+    }
+}
+
+struct TimeStampVisitor {
+    optional: bool,
+}
+
+impl<'de> serde::de::Visitor<'de> for TimeStampVisitor {
+    type Value = Option<TimeStamp>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a datetime string or unix timestamp")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if value.is_empty() && self.optional {
+            return Ok(None);
+        }
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        TimeStamp::from_unix(value)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        i64::try_from(value)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(value), &self))
+            .and_then(|value| self.visit_i64(value))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match self.optional {
+            true => Ok(None),
+            false => Err(E::invalid_type(serde::de::Unexpected::Option, &self)),
+        }
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+// For fields Appwrite reports as `""` until they are first set, like `passwordUpdate`.
+// `None` goes back out as `""` so models keep Appwrite's wire format.
+pub mod optional {
+    use super::{TimeStamp, TimeStampVisitor};
+
+    pub fn serialize<S>(value: &Option<TimeStamp>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match value {
+            Some(timestamp) => serializer.collect_str(timestamp),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<TimeStamp>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_option(TimeStampVisitor { optional: true })
+    }
+}
+
+#[test]
+fn test_timestamp_formats() {
+    let expected: TimeStamp = "2023-01-26T11:03:23.436+00:00".parse().unwrap();
+    assert_eq!(expected.unix_timestamp(), 1674731003);
+    for value in [
+        r#""2023-01-26T11:03:23.436+00:00""#,
+        r#""2023-01-26T20:03:23.436+09:00""#,
+        r#""2023-01-26T11:03:23.436Z""#,
+        r#""2023-01-26 11:03:23.436""#,
+        r#""2023-01-26T11:03:23.436""#,
+    ] {
+        let timestamp: TimeStamp = serde_json::from_str(value).unwrap();
+        assert_eq!(timestamp, expected, "{}", value);
+        assert_eq!(
+            serde_json::to_string(&timestamp).unwrap(),
+            r#""2023-01-26T11:03:23.436+00:00""#
+        );
+    }
+    let timestamp: TimeStamp = serde_json::from_str("1674731003").unwrap();
+    assert_eq!(timestamp.unix_timestamp(), 1674731003);
+    assert!(serde_json::from_str::<TimeStamp>(r#""""#).is_err());
+    assert!(serde_json::from_str::<TimeStamp>(r#""yesterday""#).is_err());
+    assert_eq!(expected.to_local(), expected.to_utc());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_timestamp_offsets() {
+    let expected: TimeStamp = "2023-01-26T11:03:23.436+00:00".parse().unwrap();
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    assert_eq!(
+        expected.to_offset(tokyo).to_rfc3339(),
+        "2023-01-26T20:03:23.436+09:00"
+    );
+}
+
+#[cfg(not(feature = "chrono"))]
+#[test]
+fn test_timestamp_offsets() {
+    let expected: TimeStamp = "2023-01-26T11:03:23.436+00:00".parse().unwrap();
+    let tokyo = UtcOffset::from_hms(9, 0, 0).unwrap();
+    assert_eq!(
+        expected.to_offset(tokyo).format(&Rfc3339).unwrap(),
+        "2023-01-26T20:03:23.436+09:00"
+    );
+}
+
+#[test]
+fn test_optional_timestamp() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct S {
+        #[serde(with = "optional", default)]
+        at: Option<TimeStamp>,
+    }
+    let empty: S = serde_json::from_str(r#"{"at":""}"#).unwrap();
+    assert_eq!(empty, S { at: None });
+    assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"at":""}"#);
+    assert_eq!(serde_json::from_str::<S>(r#"{"at":null}"#).unwrap(), empty);
+    assert_eq!(serde_json::from_str::<S>("{}").unwrap(), empty);
+    let set: S = serde_json::from_str(r#"{"at":"2023-01-26T11:03:23.436+00:00"}"#).unwrap();
+    assert_eq!(set.at.unwrap().to_string(), "2023-01-26T11:03:23.436+00:00");
+}
+
+#[cfg(all(feature = "chrono", feature = "time"))]
+#[test]
+fn test_time_conversion() {
+    let timestamp: TimeStamp = "2023-01-26T11:03:23.436+00:00".parse().unwrap();
+    let datetime = time::OffsetDateTime::try_from(timestamp).unwrap();
+    assert_eq!(datetime.unix_timestamp(), timestamp.unix_timestamp());
+    assert_eq!(datetime.millisecond(), 436);
+    assert_eq!(TimeStamp::from(datetime), timestamp);
+}
@@ -1,4 +1,3 @@
-
 use crate::{
     client::AppWriteClient,
     error::Error,
@@ -57,11 +56,18 @@ pub struct User {
     #[serde(rename = "$updatedAt")]
     pub updated_at: TimeStamp,
     pub name: String,
+    // Only returned to API keys, and only for users with a password.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_options: Option<serde_json::Value>,
     #[serde()]
     pub registration: TimeStamp,
     pub status: bool,
-    #[serde()]
-    pub password_update: TimeStamp,
+    #[serde(with = "super::timestamp::optional", default)]
+    pub password_update: Option<TimeStamp>,
     pub email: String,
     pub phone: String,
     pub email_verification: bool,
@@ -26,6 +26,8 @@ pub struct Variable {
     pub updated_at: TimeStamp,
     pub key: String,
     pub value: String,
+    // 1.4 shares variables between resources and reports the owner as `resourceId`.
+    #[serde(alias = "resourceId")]
     pub function_id: FunctionId,
 }
 
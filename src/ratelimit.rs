@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method,
+};
+use smart_default::SmartDefault;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::transport::{RequestBody, TransportRequest};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointGroup {
+    Reads,
+    Writes,
+    Uploads,
+}
+
+impl EndpointGroup {
+    pub fn classify(request: &TransportRequest) -> Self {
+        if matches!(request.body, RequestBody::Multipart { .. })
+            || request.headers.contains_key("content-range")
+        {
+            EndpointGroup::Uploads
+        } else if matches!(request.method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            EndpointGroup::Reads
+        } else {
+            EndpointGroup::Writes
+        }
+    }
+}
+
+#[derive(Debug, Clone, SmartDefault)]
+pub struct RateLimit {
+    pub requests: Option<u32>,
+    #[default(Duration::from_secs(1))]
+    pub per: Duration,
+    pub burst: Option<u32>,
+    pub max_in_flight: Option<usize>,
+    #[default = true]
+    pub adaptive: bool,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, per: Duration) -> Self {
+        RateLimit {
+            requests: Some(requests),
+            per,
+            ..Default::default()
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Default::default()
+    }
+
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    fn refill_rate(&self) -> Option<f64> {
+        match self.requests {
+            Some(requests) if !self.per.is_zero() => Some(requests as f64 / self.per.as_secs_f64()),
+            _ => None,
+        }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.burst.or(self.requests).unwrap_or(1).max(1) as f64
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct Limiter {
+    config: RateLimit,
+    semaphore: Option<Arc<Semaphore>>,
+    state: Mutex<LimiterState>,
+    // Appwrite counts its quota per route, so running out on one route must not stall the others.
+    exhausted: Mutex<HashMap<String, Instant>>,
+}
+
+impl Limiter {
+    fn new(config: RateLimit) -> Self {
+        Limiter {
+            semaphore: config
+                .max_in_flight
+                .map(|permits| Arc::new(Semaphore::new(permits.max(1)))),
+            state: Mutex::new(LimiterState {
+                tokens: config.capacity(),
+                updated: Instant::now(),
+            }),
+            exhausted: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    async fn acquire(&self, route: &str) -> Option<OwnedSemaphorePermit> {
+        let permit = match self.semaphore {
+            Some(ref semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(delay) = self.exhausted_for(route) {
+            debug!("Server quota for {} exhausted, waiting {:?}", route, delay);
+            tokio::time::sleep(delay).await;
+        }
+        while let Some(delay) = self.reserve() {
+            debug!("Client-side rate limit reached, waiting {:?}", delay);
+            tokio::time::sleep(delay).await;
+        }
+        permit
+    }
+
+    // Takes a token if one is available, otherwise returns how long to wait for the next one.
+    fn reserve(&self) -> Option<Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let rate = self.config.refill_rate()?;
+        let elapsed = now.saturating_duration_since(state.updated).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * rate).min(self.config.capacity());
+        state.updated = now;
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - state.tokens) / rate))
+        }
+    }
+
+    fn exhausted_for(&self, route: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let mut exhausted = self.exhausted.lock().unwrap();
+        exhausted.retain(|_, until| *until > now);
+        exhausted.get(route).map(|until| *until - now)
+    }
+
+    fn observe(&self, route: &str, headers: &HeaderMap<HeaderValue>) {
+        if !self.config.adaptive {
+            return;
+        }
+        let info = match RateLimitInfo::from_headers(headers) {
+            Some(info) => info,
+            None => return,
+        };
+        if info.remaining != Some(0) {
+            return;
+        }
+        let wait = match info.reset_after() {
+            Some(wait) if !wait.is_zero() => wait,
+            _ => return,
+        };
+        let until = Instant::now() + wait;
+        let mut exhausted = self.exhausted.lock().unwrap();
+        let paused = exhausted.entry(route.to_string()).or_insert(until);
+        if *paused < until {
+            *paused = until;
+        }
+        debug!(
+            "Server quota for {} exhausted, pausing it for {:?}",
+            route, wait
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset: Option<SystemTime>,
+}
+
+impl RateLimitInfo {
+    pub fn from_headers(headers: &HeaderMap<HeaderValue>) -> Option<Self> {
+        let info = RateLimitInfo {
+            limit: header_u64(headers, "x-ratelimit-limit"),
+            remaining: header_u64(headers, "x-ratelimit-remaining"),
+            // Appwrite reports the reset as a unix timestamp in seconds.
+            reset: header_u64(headers, "x-ratelimit-reset")
+                .map(|reset| UNIX_EPOCH + Duration::from_secs(reset)),
+        };
+        if info.limit.is_none() && info.remaining.is_none() && info.reset.is_none() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+
+    pub fn reset_after(&self) -> Option<Duration> {
+        let reset = self.reset?;
+        Some(
+            reset
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+}
+
+fn header_u64(headers: &HeaderMap<HeaderValue>, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    default: Option<Limiter>,
+    groups: HashMap<EndpointGroup, Limiter>,
+}
+
+#[derive(Debug)]
+pub(crate) struct RateLimitPermit<'a> {
+    limiter: Option<&'a Limiter>,
+    route: String,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimitPermit<'_> {
+    pub(crate) fn observe(&self, headers: &HeaderMap<HeaderValue>) {
+        if let Some(limiter) = self.limiter {
+            limiter.observe(&self.route, headers);
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new(default: Option<RateLimit>, groups: HashMap<EndpointGroup, RateLimit>) -> Self {
+        RateLimiter {
+            default: default.map(Limiter::new),
+            groups: groups
+                .into_iter()
+                .map(|(group, config)| (group, Limiter::new(config)))
+                .collect(),
+        }
+    }
+
+    fn limiter(&self, request: &TransportRequest) -> Option<&Limiter> {
+        self.groups
+            .get(&EndpointGroup::classify(request))
+            .or(self.default.as_ref())
+    }
+
+    pub(crate) async fn acquire(&self, request: &TransportRequest) -> RateLimitPermit<'_> {
+        let limiter = self.limiter(request);
+        let route = format!("{} {}", request.method, request.url.path());
+        let permit = match limiter {
+            Some(limiter) => limiter.acquire(&route).await,
+            None => None,
+        };
+        RateLimitPermit {
+            limiter,
+            route,
+            _permit: permit,
+        }
+    }
+}
+
+#[test]
+fn test_token_bucket() {
+    let limiter = Limiter::new(RateLimit::new(2, Duration::from_secs(1)));
+    assert_eq!(limiter.reserve(), None);
+    assert_eq!(limiter.reserve(), None);
+    let wait = limiter.reserve().unwrap();
+    assert!(wait > Duration::ZERO && wait <= Duration::from_millis(500));
+}
+
+#[test]
+fn test_server_quota_per_route() {
+    let limiter = Limiter::new(RateLimit::unlimited());
+    let mut headers = HeaderMap::new();
+    let reset = SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + Duration::from_secs(30);
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+    headers.insert(
+        "x-ratelimit-reset",
+        HeaderValue::from_str(&reset.as_secs().to_string()).unwrap(),
+    );
+    limiter.observe("POST /v1/account/sessions/email", &headers);
+    assert!(
+        limiter
+            .exhausted_for("POST /v1/account/sessions/email")
+            .unwrap()
+            > Duration::from_secs(25)
+    );
+    assert_eq!(limiter.exhausted_for("GET /v1/account"), None);
+    assert_eq!(limiter.reserve(), None);
+
+    let limiter = Limiter::new(RateLimit::unlimited().adaptive(false));
+    limiter.observe("POST /v1/account/sessions/email", &headers);
+    assert_eq!(
+        limiter.exhausted_for("POST /v1/account/sessions/email"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_max_in_flight_per_group() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .group_rate_limit(
+            EndpointGroup::Uploads,
+            RateLimit::unlimited().max_in_flight(1),
+        )
+        .build()
+        .unwrap();
+    let limiter = client.rate_limiter().unwrap();
+    let upload = TransportRequest {
+        method: Method::POST,
+        url: url::Url::parse("http://localhost/v1/storage/buckets/b/files").unwrap(),
+        headers: HeaderMap::new(),
+        body: RequestBody::Empty,
+        timeout: None,
+    };
+    let mut chunk = upload.clone();
+    chunk
+        .headers
+        .insert("content-range", HeaderValue::from_static("bytes 0-1/2"));
+    let _held = limiter.acquire(&chunk).await;
+    let blocked =
+        tokio::time::timeout(Duration::from_millis(20), limiter.acquire(&chunk.clone())).await;
+    assert!(blocked.is_err());
+
+    // Reads have no limit configured and must not wait on the upload permit held above.
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    tokio::time::timeout(Duration::from_secs(1), HealthService::get_http(&client))
+        .await
+        .expect("reads waited on the upload permit")
+        .unwrap();
+    assert_eq!(EndpointGroup::classify(&upload), EndpointGroup::Writes);
+}
@@ -0,0 +1,147 @@
+use reqwest::header::{HeaderMap, HeaderValue};
+use url::Url;
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+pub(crate) fn is_sensitive(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    [
+        "password",
+        "secret",
+        "token",
+        "jwt",
+        "cookie",
+        "authorization",
+    ]
+    .iter()
+    .any(|word| name.contains(word))
+        || name.ends_with("-key")
+        || name.ends_with("_key")
+        || name.ends_with("apikey")
+}
+
+pub(crate) fn redact_url(url: &Url) -> String {
+    let mut path = url.path().to_string();
+    if url.query().is_some() {
+        let query = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if is_sensitive(&key) {
+                    REDACTED.into()
+                } else {
+                    value
+                };
+                format!("{}={}", key, value)
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        path.push('?');
+        path.push_str(&query);
+    }
+    path
+}
+
+pub(crate) fn redact_headers(headers: &HeaderMap<HeaderValue>) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+// Response fields that carry credentials. Matching names like `is_sensitive` does would also hit
+// timestamps such as `passwordUpdate` and `providerAccessTokenExpiry`, which then fail to parse.
+const SECRET_FIELDS: &[&str] = &[
+    "secret",
+    "jwt",
+    "password",
+    "hash",
+    "providerAccessToken",
+    "providerRefreshToken",
+];
+
+pub(crate) fn redact_json(value: &serde_json::Value) -> serde_json::Value {
+    redact_json_fields(value, &is_sensitive)
+}
+
+pub(crate) fn redact_response_json(value: &serde_json::Value) -> serde_json::Value {
+    redact_json_fields(value, &|key| SECRET_FIELDS.contains(&key))
+}
+
+fn redact_json_fields(
+    value: &serde_json::Value,
+    is_secret: &dyn Fn(&str) -> bool,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_secret(key) && !value.is_null() {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else {
+                    redact_json_fields(value, is_secret)
+                };
+                (key.clone(), value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| redact_json_fields(item, is_secret))
+            .collect(),
+        value => value.clone(),
+    }
+}
+
+#[test]
+fn test_redact_url() {
+    let url = Url::parse("http://localhost/v1/account/sessions/magic-url?userId=u1&secret=s3cr3t")
+        .unwrap();
+    assert_eq!(
+        redact_url(&url),
+        "/v1/account/sessions/magic-url?userId=u1&secret=[REDACTED]"
+    );
+    assert!(is_sensitive("X-Appwrite-Key"));
+    assert!(is_sensitive("oldPassword"));
+    assert!(!is_sensitive("key"));
+}
+
+#[test]
+fn test_redact_json() {
+    let value = serde_json::json!({
+        "$id": "u1",
+        "password": "$argon2id$v=19$...",
+        "sessions": [{ "providerAccessToken": "abc", "provider": "github" }],
+        "hash": null,
+    });
+    assert_eq!(
+        redact_json(&value),
+        serde_json::json!({
+            "$id": "u1",
+            "password": "[REDACTED]",
+            "sessions": [{ "providerAccessToken": "[REDACTED]", "provider": "github" }],
+            "hash": null,
+        })
+    );
+
+    let value = serde_json::json!({
+        "password": "$argon2id$v=19$...",
+        "passwordUpdate": "2023-01-26T11:03:23.436+00:00",
+        "providerAccessToken": "abc",
+        "providerAccessTokenExpiry": "2023-02-02T11:03:23.436+00:00",
+    });
+    assert_eq!(
+        redact_response_json(&value),
+        serde_json::json!({
+            "password": "[REDACTED]",
+            "passwordUpdate": "2023-01-26T11:03:23.436+00:00",
+            "providerAccessToken": "[REDACTED]",
+            "providerAccessTokenExpiry": "2023-02-02T11:03:23.436+00:00",
+        })
+    );
+}
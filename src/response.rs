@@ -0,0 +1,96 @@
+use std::{cell::RefCell, future::Future, ops::Deref, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method, StatusCode,
+};
+
+use crate::{error::Error, ratelimit::RateLimitInfo};
+
+tokio::task_local! {
+    static RESPONSE_META: RefCell<Option<ResponseMeta>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub method: Method,
+    pub path: String,
+    pub status: StatusCode,
+    pub headers: HeaderMap<HeaderValue>,
+    pub elapsed: Duration,
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub value: T,
+    pub meta: ResponseMeta,
+}
+
+impl<T> Response<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn into_parts(self) -> (T, ResponseMeta) {
+        (self.value, self.meta)
+    }
+}
+
+impl<T> Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+pub trait ResponseExt<T>: Future<Output = Result<T, Error>> + Sized {
+    // Calls that send several requests, like chunked uploads, report the last one.
+    fn with_meta(self) -> impl Future<Output = Result<Response<T>, Error>> {
+        RESPONSE_META.scope(RefCell::new(None), async move {
+            let value = self.await?;
+            let meta = RESPONSE_META
+                .with(|meta| meta.borrow_mut().take())
+                .ok_or(Error::MissingResponseMeta)?;
+            Ok(Response { value, meta })
+        })
+    }
+}
+
+impl<T, F: Future<Output = Result<T, Error>>> ResponseExt<T> for F {}
+
+pub(crate) fn record<F: FnOnce() -> ResponseMeta>(meta: F) {
+    let _ = RESPONSE_META.try_with(|slot| *slot.borrow_mut() = Some(meta()));
+}
+
+#[tokio::test]
+async fn test_with_meta() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let transport = MemoryTransport::new();
+    let mut headers = HeaderMap::new();
+    headers.insert("x-ratelimit-limit", HeaderValue::from_static("60"));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("59"));
+    headers.insert("x-ratelimit-reset", HeaderValue::from_static("1672531200"));
+    headers.insert("content-type", HeaderValue::from_static("application/json"));
+    transport.push_response(
+        StatusCode::OK,
+        headers,
+        serde_json::to_vec(&serde_json::json!({ "ping": 1, "status": "pass" })).unwrap(),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let response = HealthService::get_http(&client).with_meta().await.unwrap();
+    assert_eq!(response.ping, 1);
+    assert_eq!(response.meta.status, StatusCode::OK);
+    assert_eq!(response.meta.path, "/v1/health");
+    let rate_limit = response.meta.rate_limit.unwrap();
+    assert_eq!(rate_limit.limit, Some(60));
+    assert_eq!(rate_limit.remaining, Some(59));
+
+    let result = async { Ok::<_, Error>(()) }.with_meta().await;
+    assert!(matches!(result, Err(Error::MissingResponseMeta)));
+}
@@ -0,0 +1,233 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, RETRY_AFTER},
+    Method, StatusCode,
+};
+use smart_default::SmartDefault;
+
+use crate::{error::Error, transport::TransportRequest};
+
+#[derive(Debug, Clone, SmartDefault)]
+pub struct RetryPolicy {
+    #[default = 3]
+    pub max_attempts: u32,
+    #[default(Duration::from_millis(200))]
+    pub base_delay: Duration,
+    #[default(Duration::from_secs(10))]
+    pub max_delay: Duration,
+    #[default = true]
+    pub jitter: bool,
+    #[default = true]
+    pub respect_retry_after: bool,
+    pub retry_non_idempotent: bool,
+    #[default(vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ])]
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    pub fn retry_statuses(mut self, retry_statuses: Vec<StatusCode>) -> Self {
+        self.retry_statuses = retry_statuses;
+        self
+    }
+
+    pub fn is_retryable_request(&self, request: &TransportRequest) -> bool {
+        // Chunks after the first carry the file id the server assigned, so sending one twice only
+        // overwrites the same range. Retrying the first chunk of a `unique()` upload would create
+        // a second file.
+        self.retry_non_idempotent
+            || is_idempotent(&request.method)
+            || (request.headers.contains_key("content-range")
+                && request.headers.contains_key("x-appwrite-id"))
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    pub fn is_retryable_error(&self, error: &Error) -> bool {
+        error.retryable()
+    }
+
+    // None when the server asks for a longer wait than `max_delay`: retrying any sooner would only
+    // be throttled again, so the response is handed back as is.
+    pub fn delay(
+        &self,
+        attempt: u32,
+        headers: Option<&HeaderMap<HeaderValue>>,
+    ) -> Option<Duration> {
+        if self.respect_retry_after {
+            if let Some(delay) = headers.and_then(retry_after) {
+                return (delay <= self.max_delay).then_some(delay);
+            }
+        }
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let delay = exp.min(self.max_delay);
+        if self.jitter && !delay.is_zero() {
+            let half = delay / 2;
+            Some(half + rand::thread_rng().gen_range(Duration::ZERO..=half))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE | Method::TRACE
+    )
+}
+
+pub(crate) fn retry_after(headers: &HeaderMap<HeaderValue>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    #[cfg(feature = "chrono")]
+    let delta = chrono::DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .signed_duration_since(chrono::Utc::now())
+        .to_std();
+    #[cfg(not(feature = "chrono"))]
+    let delta = Duration::try_from(
+        time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc2822).ok()?
+            - time::OffsetDateTime::now_utc(),
+    );
+    Some(delta.unwrap_or(Duration::ZERO))
+}
+
+#[test]
+fn test_retry_after() {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+    );
+    assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+    let policy = RetryPolicy::default().jitter(false);
+    assert_eq!(policy.delay(1, None), Some(Duration::from_millis(200)));
+    assert_eq!(policy.delay(3, None), Some(Duration::from_millis(800)));
+    assert_eq!(policy.delay(10, None), Some(Duration::from_secs(10)));
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("10"));
+    assert_eq!(
+        policy.delay(1, Some(&headers)),
+        Some(Duration::from_secs(10))
+    );
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("11"));
+    assert_eq!(policy.delay(1, Some(&headers)), None);
+}
+
+#[test]
+fn test_retryable_chunks() {
+    use crate::transport::RequestBody;
+
+    let mut request = TransportRequest {
+        method: Method::POST,
+        url: "http://localhost/v1/storage/buckets/bucket/files"
+            .parse()
+            .unwrap(),
+        headers: HeaderMap::new(),
+        body: RequestBody::Empty,
+        timeout: None,
+    };
+    let policy = RetryPolicy::default();
+    assert!(!policy.is_retryable_request(&request));
+    request.headers.insert(
+        "content-range",
+        HeaderValue::from_static("bytes 0-5242879/10485760"),
+    );
+    assert!(!policy.is_retryable_request(&request));
+    request
+        .headers
+        .insert("x-appwrite-id", HeaderValue::from_static("file"));
+    assert!(policy.is_retryable_request(&request));
+}
+
+#[tokio::test]
+async fn test_retry_transient_status() {
+    use crate::{prelude::*, transport::MemoryTransport};
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::SERVICE_UNAVAILABLE,
+        serde_json::json!({ "message": "", "code": 503, "type": "general_server_error", "version": "1.2.0" }),
+    );
+    transport.push_error("connection reset");
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy::new(3).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    HealthService::get_http(&client).await.unwrap();
+    assert_eq!(transport.requests().len(), 3);
+
+    transport.push_json(
+        StatusCode::TOO_MANY_REQUESTS,
+        serde_json::json!({ "message": "", "code": 429, "type": "general_rate_limit_exceeded", "version": "1.2.0" }),
+    );
+    let result = AccountsService::create_email_verification(&client, "http://localhost").await;
+    assert!(result.is_err());
+    assert_eq!(transport.requests().len(), 4);
+
+    // A Retry-After beyond `max_delay` gives up instead of coming back early.
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+    transport.push_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        headers,
+        r#"{"message":"","code":429,"type":"general_rate_limit_exceeded","version":"1.2.0"}"#,
+    );
+    let error = HealthService::get_http(&client).await.unwrap_err();
+    assert!(error.is_rate_limited());
+    assert_eq!(transport.requests().len(), 5);
+}
@@ -15,7 +15,52 @@ use crate::{
 };
 pub struct AccountsService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl AccountsService {
+    pub async fn create_account(
+        client: &AppWriteClient,
+        user_id: &UserId,
+        email: &str,
+        password: &str,
+        name: Option<&str>,
+    ) -> Result<User, Error> {
+        let url = "/account";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::json!({
+                    "userId": user_id,
+                    "email": email,
+                    "password": password,
+                    "name": name
+                })),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    // The client keeps the session cookie from the response, later calls on it act as this user.
+    pub async fn create_email_session(
+        client: &AppWriteClient,
+        email: &str,
+        password: &str,
+    ) -> Result<Session, Error> {
+        let url = "/account/sessions/email";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::json!({
+                    "email": email,
+                    "password": password
+                })),
+            )
+            .await?;
+        Ok(check_response!(Session: response))
+    }
+
     pub async fn get_account(client: &AppWriteClient) -> Result<User, Error> {
         let url = "/account";
         let response = client.call(Method::GET, url, RequestData::None).await?;
@@ -173,10 +218,20 @@ impl AccountsService {
         Ok(check_response!(Session: response))
     }
 
+    pub async fn delete_current_session(client: &AppWriteClient) -> Result<(), Error> {
+        let url = "/account/sessions/current";
+        let response = client.call(Method::DELETE, url, RequestData::None).await?;
+        check_response!(response);
+        client.clear_session().await;
+        Ok(())
+    }
+
     pub async fn delete_sessions(client: &AppWriteClient) -> Result<(), Error> {
         let url = "/account/sessions";
         let response = client.call(Method::DELETE, url, RequestData::None).await?;
-        Ok(check_response!(response))
+        check_response!(response);
+        client.clear_session().await;
+        Ok(())
     }
 
     pub async fn password_recovery(
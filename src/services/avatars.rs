@@ -9,6 +9,8 @@ use crate::{
 
 pub struct AvatarService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl AvatarService {
     pub async fn get_creditcard_icon(
         client: &AppWriteClient,
@@ -3,7 +3,6 @@ use reqwest::Method;
 use crate::{
     check_response,
     client::{AppWriteClient, RequestData},
-    error::Error,
     models::{
         attribute::Attribute,
         collection::{Collection, CollectionId},
@@ -72,6 +71,8 @@ pub struct UpdateDocumentPayload {
     pub permissions: Vec<Permission>,
 }
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl DatabasesService {
     pub async fn create_database(
         client: &AppWriteClient,
@@ -296,7 +297,7 @@ impl DatabasesService {
                 RequestData::Json(serde_json::to_value(payload)?),
             )
             .await?;
-        Ok(check_response!(Document<T>: response))
+        Ok(check_response!(Document<T>: response).or_database_id(database_id))
     }
 
     pub async fn list_documents<T>(
@@ -315,11 +316,16 @@ impl DatabasesService {
                 params.push(("queries[]".into(), q.to_string()));
             }
         }
-        println!("{}", params[0].1);
         let response = client
             .call(Method::GET, &url, RequestData::Params(params))
             .await?;
-        Ok(check_response!(ListResponse<Document<T>>: response))
+        let mut documents = check_response!(ListResponse<Document<T>>: response);
+        documents.items = documents
+            .items
+            .into_iter()
+            .map(|document| document.or_database_id(database_id))
+            .collect();
+        Ok(documents)
     }
 
     pub async fn get_document<T>(
@@ -334,7 +340,7 @@ impl DatabasesService {
         let url =
             format!("/databases/{database_id}/collections/{collection_id}/documents/{document_id}");
         let response = client.call(Method::GET, &url, RequestData::None).await?;
-        Ok(check_response!(Document<T>: response))
+        Ok(check_response!(Document<T>: response).or_database_id(database_id))
     }
 
     pub async fn update_document<T>(
@@ -356,7 +362,7 @@ impl DatabasesService {
                 RequestData::Json(serde_json::to_value(payload)?),
             )
             .await?;
-        Ok(check_response!(Document<T>: response))
+        Ok(check_response!(Document<T>: response).or_database_id(database_id))
     }
 
     pub async fn delete_document(
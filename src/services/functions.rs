@@ -48,6 +48,8 @@ pub struct UpdateFunctionPayload {
 
 pub struct FunctionsService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl FunctionsService {
     pub async fn create_function(
         client: &AppWriteClient,
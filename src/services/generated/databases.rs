@@ -0,0 +1,35 @@
+// @generated by `cargo run -p codegen` from helper/codegen/spec/swagger2-1.2.x-server.json.
+// Do not edit by hand: put hand-written bindings in src/services/databases.rs and list them in
+// helper/codegen/overrides.toml.
+
+use reqwest::Method;
+
+use crate::{check_response, client::RequestData, prelude::*};
+
+#[cfg_attr(
+    feature = "blocking",
+    blocking_macro::blocking_service(crate::services::databases::blocking::DatabasesService)
+)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
+impl DatabasesService {
+    pub async fn list_attributes(
+        client: &AppWriteClient,
+        database_id: &DatabaseId,
+        collection_id: &CollectionId,
+    ) -> Result<ListResponse<Attribute>, crate::error::Error> {
+        let url = format!("/databases/{database_id}/collections/{collection_id}/attributes");
+        let response = client.call(Method::GET, &url, RequestData::None).await?;
+        Ok(check_response!(ListResponse<Attribute>: response))
+    }
+
+    pub async fn get_attribute(
+        client: &AppWriteClient,
+        database_id: &DatabaseId,
+        collection_id: &CollectionId,
+        key: &str,
+    ) -> Result<Attribute, crate::error::Error> {
+        let url = format!("/databases/{database_id}/collections/{collection_id}/attributes/{key}");
+        let response = client.call(Method::GET, &url, RequestData::None).await?;
+        Ok(check_response!(Attribute: response))
+    }
+}
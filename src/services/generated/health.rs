@@ -0,0 +1,46 @@
+// @generated by `cargo run -p codegen` from helper/codegen/spec/swagger2-1.2.x-server.json.
+// Do not edit by hand: put hand-written bindings in src/services/health.rs and list them in
+// helper/codegen/overrides.toml.
+
+use reqwest::Method;
+
+use crate::{check_response, client::RequestData, prelude::*};
+
+#[cfg_attr(
+    feature = "blocking",
+    blocking_macro::blocking_service(crate::services::health::blocking::HealthService)
+)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
+impl HealthService {
+    pub async fn get_queue_certificates(
+        client: &AppWriteClient,
+    ) -> Result<HealthQueue, crate::error::Error> {
+        let url = "/health/queue/certificates";
+        let response = client.call(Method::GET, url, RequestData::None).await?;
+        Ok(check_response!(HealthQueue: response))
+    }
+
+    pub async fn get_queue_functions(
+        client: &AppWriteClient,
+    ) -> Result<HealthQueue, crate::error::Error> {
+        let url = "/health/queue/functions";
+        let response = client.call(Method::GET, url, RequestData::None).await?;
+        Ok(check_response!(HealthQueue: response))
+    }
+
+    pub async fn get_queue_logs(
+        client: &AppWriteClient,
+    ) -> Result<HealthQueue, crate::error::Error> {
+        let url = "/health/queue/logs";
+        let response = client.call(Method::GET, url, RequestData::None).await?;
+        Ok(check_response!(HealthQueue: response))
+    }
+
+    pub async fn get_queue_webhooks(
+        client: &AppWriteClient,
+    ) -> Result<HealthQueue, crate::error::Error> {
+        let url = "/health/queue/webhooks";
+        let response = client.call(Method::GET, url, RequestData::None).await?;
+        Ok(check_response!(HealthQueue: response))
+    }
+}
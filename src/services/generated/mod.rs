@@ -0,0 +1,14 @@
+// @generated by `cargo run -p codegen` from helper/codegen/spec/swagger2-1.2.x-server.json.
+// Do not edit by hand: put hand-written bindings in src/services/<service>.rs and list them in
+// helper/codegen/overrides.toml.
+
+pub mod databases;
+pub mod health;
+pub mod users;
+
+pub mod prelude {
+    pub use super::users::{
+        CreateArgon2UserPayload, CreateBcryptUserPayload, CreatePhPassUserPayload,
+        CreateScryptModifiedUserPayload, CreateScryptUserPayload, CreateShaUserPayload,
+    };
+}
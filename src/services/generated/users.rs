@@ -0,0 +1,174 @@
+// @generated by `cargo run -p codegen` from helper/codegen/spec/swagger2-1.2.x-server.json.
+// Do not edit by hand: put hand-written bindings in src/services/users.rs and list them in
+// helper/codegen/overrides.toml.
+
+use reqwest::Method;
+
+use crate::{check_response, client::RequestData, prelude::*};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateArgon2UserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBcryptUserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePhPassUserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateScryptUserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    pub password_salt: String,
+    pub password_cpu: i64,
+    pub password_memory: i64,
+    pub password_parallel: i64,
+    pub password_length: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateScryptModifiedUserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    pub password_salt: String,
+    pub password_salt_separator: String,
+    pub password_signer_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateShaUserPayload {
+    pub user_id: UserId,
+    pub email: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[cfg_attr(
+    feature = "blocking",
+    blocking_macro::blocking_service(crate::services::users::blocking::UsersService)
+)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
+impl UsersService {
+    pub async fn create_argon2_user(
+        client: &AppWriteClient,
+        payload: CreateArgon2UserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/argon2";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    pub async fn create_bcrypt_user(
+        client: &AppWriteClient,
+        payload: CreateBcryptUserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/bcrypt";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    pub async fn create_ph_pass_user(
+        client: &AppWriteClient,
+        payload: CreatePhPassUserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/phpass";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    pub async fn create_scrypt_user(
+        client: &AppWriteClient,
+        payload: CreateScryptUserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/scrypt";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    pub async fn create_scrypt_modified_user(
+        client: &AppWriteClient,
+        payload: CreateScryptModifiedUserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/scrypt-modified";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+
+    pub async fn create_sha_user(
+        client: &AppWriteClient,
+        payload: CreateShaUserPayload,
+    ) -> Result<User, crate::error::Error> {
+        let url = "/users/sha";
+        let response = client
+            .call(
+                Method::POST,
+                url,
+                RequestData::Json(serde_json::to_value(payload)?),
+            )
+            .await?;
+        Ok(check_response!(User: response))
+    }
+}
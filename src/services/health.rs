@@ -4,11 +4,13 @@ use crate::{
     check_response,
     client::{AppWriteClient, RequestData},
     error::Error,
-    models::health::{Health, HealthAntivirus, HealthTime},
+    models::health::{Health, HealthAntivirus, HealthTime, HealthVersion},
 };
 
 pub struct HealthService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl HealthService {
     pub async fn get_http(client: &AppWriteClient) -> Result<Health, Error> {
         let url = "/health";
@@ -28,6 +30,12 @@ impl HealthService {
         Ok(check_response!(Health: response))
     }
 
+    pub async fn get_version(client: &AppWriteClient) -> Result<HealthVersion, Error> {
+        let url = "/health/version";
+        let response = client.call(Method::GET, url, RequestData::None).await?;
+        Ok(check_response!(HealthVersion: response))
+    }
+
     pub async fn get_time(client: &AppWriteClient) -> Result<HealthTime, Error> {
         let url = "/health/time";
         let response = client.call(Method::GET, url, RequestData::None).await?;
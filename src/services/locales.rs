@@ -12,6 +12,8 @@ use crate::{
 
 pub struct LocalesService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl LocalesService {
     pub async fn get_user_locale(client: &AppWriteClient) -> Result<Locale, crate::error::Error> {
         let url = "/locale";
@@ -2,6 +2,7 @@ pub mod accounts;
 pub mod avatars;
 pub mod databases;
 pub mod functions;
+pub mod generated;
 pub mod health;
 pub mod locales;
 pub mod storages;
@@ -17,6 +18,7 @@ pub enum CursorDirection {
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum Order {
     #[display(fmt = "asc")]
     Asc,
@@ -26,7 +28,7 @@ pub enum Order {
 
 #[derive(Debug, Clone, Default, SerializeParams)]
 pub struct SearchPayload<ID> {
-    pub seatch: Option<String>,
+    pub search: Option<String>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
     pub cursor: Option<ID>,
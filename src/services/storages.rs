@@ -70,6 +70,8 @@ pub struct UpdateBucketPayload {
     pub antivirus: Option<bool>,
 }
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl StoragesService {
     pub async fn create_bucket(
         client: &AppWriteClient,
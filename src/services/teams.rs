@@ -3,7 +3,6 @@ use reqwest::Method;
 use crate::{
     check_response,
     client::{AppWriteClient, RequestData},
-    error::Error,
     models::{
         membership::{Membership, MembershipId},
         team::{Team, TeamId},
@@ -34,6 +33,8 @@ pub struct CreateMembershipPayload {
     pub name: Option<String>,
 }
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl TeamsService {
     pub async fn create_team(
         client: &AppWriteClient,
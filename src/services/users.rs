@@ -17,6 +17,8 @@ use crate::{
 use super::SearchPayload;
 pub struct UsersService;
 
+#[cfg_attr(feature = "blocking", blocking_macro::blocking_service)]
+#[cfg_attr(feature = "tracing", trace_macro::instrument_service)]
 impl UsersService {
     pub async fn create_user(
         client: &AppWriteClient,
@@ -250,7 +252,7 @@ impl UsersService {
             .call(
                 Method::PATCH,
                 &url,
-                RequestData::Json(serde_json::json!(payload)),
+                RequestData::Json(serde_json::json!({ "prefs": payload })),
             )
             .await?;
         Ok(check_response!(User: response))
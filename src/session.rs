@@ -0,0 +1,384 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, SET_COOKIE};
+
+use crate::error::Error;
+
+const SESSION_COOKIE_PREFIX: &str = "a_session_";
+const FALLBACK_COOKIES: &str = "x-fallback-cookies";
+
+// What Appwrite hands out on login: the `a_session_<project>` cookies and the `X-Fallback-Cookies`
+// header, which carries the same secret for clients that can't keep cookies.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCookies {
+    pub cookies: BTreeMap<String, String>,
+    pub fallback: Option<String>,
+}
+
+impl SessionCookies {
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty() && self.fallback.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.cookies.clear();
+        self.fallback = None;
+    }
+
+    // Logging out answers with emptied cookies and `[]` as fallback, which drops them here.
+    // Returns whether anything changed.
+    pub(crate) fn capture(&mut self, headers: &HeaderMap<HeaderValue>) -> bool {
+        let before = self.clone();
+        for value in headers.get_all(SET_COOKIE) {
+            let cookie = match value.to_str() {
+                Ok(cookie) => cookie,
+                Err(_) => continue,
+            };
+            let pair = cookie.split(';').next().unwrap_or_default();
+            let (name, value) = match pair.split_once('=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => continue,
+            };
+            if !name.starts_with(SESSION_COOKIE_PREFIX) {
+                continue;
+            }
+            if value.is_empty() {
+                self.cookies.remove(name);
+            } else {
+                self.cookies.insert(name.to_string(), value.to_string());
+            }
+        }
+        if let Some(fallback) = headers.get(FALLBACK_COOKIES) {
+            let fallback = fallback.to_str().unwrap_or_default();
+            self.fallback = match serde_json::from_str::<serde_json::Value>(fallback) {
+                Ok(serde_json::Value::Object(cookies)) if !cookies.is_empty() => {
+                    Some(fallback.to_string())
+                }
+                _ => None,
+            };
+        }
+        *self != before
+    }
+
+    pub(crate) fn apply(&self, headers: &mut HeaderMap<HeaderValue>) {
+        if !self.cookies.is_empty() && !headers.contains_key(COOKIE) {
+            let cookie = self
+                .cookies
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; ");
+            if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+                headers.insert(COOKIE, cookie);
+            }
+        }
+        if let Some(ref fallback) = self.fallback {
+            if let Ok(fallback) = HeaderValue::from_str(fallback) {
+                headers.insert(FALLBACK_COOKIES, fallback);
+            }
+        }
+    }
+}
+
+// Keeps a client's session across restarts. The client loads it once when built and saves it
+// whenever a response logs in or out; implement this to keep sessions in a keychain or database.
+pub trait SessionStore: fmt::Debug + Send + Sync {
+    fn load(&self) -> Result<Option<SessionCookies>, Error>;
+    fn save(&self, session: &SessionCookies) -> Result<(), Error>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemorySessionStore {
+    session: Arc<Mutex<Option<SessionCookies>>>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> Option<SessionCookies> {
+        self.session.lock().unwrap().clone()
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn load(&self) -> Result<Option<SessionCookies>, Error> {
+        Ok(self.get())
+    }
+
+    fn save(&self, session: &SessionCookies) -> Result<(), Error> {
+        *self.session.lock().unwrap() = Some(session.clone()).filter(|s| !s.is_empty());
+        Ok(())
+    }
+}
+
+// Stores the session as JSON, removing the file again on logout.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileSessionStore {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn load(&self) -> Result<Option<SessionCookies>, Error> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn save(&self, session: &SessionCookies) -> Result<(), Error> {
+        if session.is_empty() {
+            return match std::fs::remove_file(&self.path) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // The cookies are as good as a password: keep them private to the user, and write them
+        // next to the target first so a crash never leaves a half-written session behind.
+        let content = serde_json::to_vec_pretty(session)?;
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(format!(".{}.tmp", std::process::id()));
+        let temp = PathBuf::from(temp);
+        // A leftover from a crashed save could have other permissions, so never reuse it.
+        let _ = std::fs::remove_file(&temp);
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let written = options
+            .open(&temp)
+            .and_then(|mut file| {
+                std::io::Write::write_all(&mut file, &content)?;
+                file.sync_all()
+            })
+            .and_then(|_| std::fs::rename(&temp, &self.path));
+        if let Err(error) = written {
+            let _ = std::fs::remove_file(&temp);
+            return Err(error.into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_capture_session_cookies() {
+    let mut headers = HeaderMap::new();
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static(
+            "a_session_project_legacy=secret; expires=Fri, 26-Jan-2024 11:03:23 GMT; path=/; httponly",
+        ),
+    );
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=secret; path=/; secure; httponly"),
+    );
+    headers.append(SET_COOKIE, HeaderValue::from_static("tracking=1; path=/"));
+    headers.insert(
+        FALLBACK_COOKIES,
+        HeaderValue::from_static(r#"{"a_session_project":"secret"}"#),
+    );
+    let mut session = SessionCookies::default();
+    assert!(session.capture(&headers));
+    assert_eq!(session.cookies.len(), 2);
+    assert!(!session.capture(&headers));
+
+    let mut request = HeaderMap::new();
+    session.apply(&mut request);
+    assert_eq!(
+        request[COOKIE],
+        "a_session_project=secret; a_session_project_legacy=secret"
+    );
+    assert_eq!(
+        request[FALLBACK_COOKIES],
+        r#"{"a_session_project":"secret"}"#
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static(
+            "a_session_project_legacy=; expires=Thu, 01-Jan-1970 00:00:00 GMT",
+        ),
+    );
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=; expires=Thu, 01-Jan-1970 00:00:00 GMT"),
+    );
+    headers.insert(FALLBACK_COOKIES, HeaderValue::from_static("[]"));
+    assert!(session.capture(&headers));
+    assert!(session.is_empty());
+}
+
+#[test]
+fn test_file_session_store() {
+    let path = std::env::temp_dir()
+        .join(format!("appwrite-session-test-{}", std::process::id()))
+        .join("session.json");
+    let store = FileSessionStore::new(&path);
+    store.save(&SessionCookies::default()).unwrap();
+    assert_eq!(store.load().unwrap(), None);
+
+    let session = SessionCookies {
+        cookies: maplit::btreemap! {
+            "a_session_project".to_string() => "secret".to_string(),
+        },
+        fallback: Some(r#"{"a_session_project":"secret"}"#.to_string()),
+    };
+    store.save(&session).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    assert_eq!(FileSessionStore::new(&path).load().unwrap(), Some(session));
+    store.save(&SessionCookies::default()).unwrap();
+    assert!(!path.exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_client_session_store() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    let store = MemorySessionStore::new();
+    store
+        .save(&SessionCookies {
+            cookies: maplit::btreemap! {
+                "a_session_project".to_string() => "restored".to_string(),
+            },
+            fallback: None,
+        })
+        .unwrap();
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .session_store(store.clone())
+        .build()
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=fresh; path=/; httponly"),
+    );
+    headers.insert(
+        FALLBACK_COOKIES,
+        HeaderValue::from_static(r#"{"a_session_project":"fresh"}"#),
+    );
+    transport.push_response(StatusCode::OK, headers, r#"{"ping":1,"status":"pass"}"#);
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    HealthService::get_http(&client).await.unwrap();
+    HealthService::get_http(&client).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].headers[COOKIE], "a_session_project=restored");
+    assert_eq!(requests[1].headers[COOKIE], "a_session_project=fresh");
+    let saved = store.get().unwrap();
+    assert_eq!(saved.cookies["a_session_project"], "fresh");
+    assert!(saved.fallback.is_some());
+
+    // A key or JWT says who the request acts as, so the session stays out of it.
+    let mut headers = HeaderMap::new();
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=other; path=/; httponly"),
+    );
+    transport.push_response(StatusCode::OK, headers, r#"{"ping":1,"status":"pass"}"#);
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    HealthService::get_http(&client.with_key("server-key").unwrap())
+        .await
+        .unwrap();
+    HealthService::get_http(&client.with_jwt("user-jwt").unwrap())
+        .await
+        .unwrap();
+    let requests = transport.requests();
+    for request in &requests[2..] {
+        assert!(!request.headers.contains_key(COOKIE));
+        assert!(!request.headers.contains_key(FALLBACK_COOKIES));
+    }
+    assert_eq!(client.session().cookies["a_session_project"], "fresh");
+
+    // Logging out a JWT ends that user's session on the server, not the one shared here.
+    transport.push_response(StatusCode::NO_CONTENT, HeaderMap::new(), "");
+    AccountsService::delete_current_session(&client.with_jwt("user-jwt").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(client.session().cookies["a_session_project"], "fresh");
+    assert_eq!(store.get().unwrap().cookies["a_session_project"], "fresh");
+
+    client.clear_session().await;
+    assert_eq!(store.get(), None);
+}
+
+// Saving may block, so it has to happen off the thread driving the request.
+#[tokio::test]
+async fn test_session_saved_off_the_runtime() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use reqwest::StatusCode;
+
+    #[derive(Debug, Default)]
+    struct ThreadStore(Mutex<Vec<std::thread::ThreadId>>);
+
+    impl SessionStore for Arc<ThreadStore> {
+        fn load(&self) -> Result<Option<SessionCookies>, Error> {
+            Ok(None)
+        }
+
+        fn save(&self, _: &SessionCookies) -> Result<(), Error> {
+            self.0.lock().unwrap().push(std::thread::current().id());
+            Ok(())
+        }
+    }
+
+    let store = Arc::new(ThreadStore::default());
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .session_store(store.clone())
+        .build()
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("a_session_project=fresh; path=/; httponly"),
+    );
+    transport.push_response(StatusCode::OK, headers, r#"{"ping":1,"status":"pass"}"#);
+    HealthService::get_http(&client).await.unwrap();
+    client.clear_session().await;
+
+    let threads = store.0.lock().unwrap();
+    assert_eq!(threads.len(), 2);
+    assert!(threads
+        .iter()
+        .all(|thread| *thread != std::thread::current().id()));
+}
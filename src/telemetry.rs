@@ -0,0 +1,245 @@
+use reqwest::{header::CONTENT_LENGTH, StatusCode};
+
+use crate::{
+    error::{Error, RequestContext},
+    transport::{RequestBody, TransportRequest, TransportResponse},
+};
+
+pub(crate) fn record_request(context: &RequestContext, request: &mut TransportRequest) {
+    let span = tracing::Span::current();
+    let length = match request.body {
+        RequestBody::Empty => 0,
+        RequestBody::Json(ref json) | RequestBody::Form(ref json) => json.to_string().len(),
+        RequestBody::Multipart {
+            ref fields,
+            ref file,
+        } => {
+            fields
+                .iter()
+                .map(|(key, value)| key.len() + value.len())
+                .sum::<usize>()
+                + file.data.len()
+        }
+    };
+    span.record("http.method", request.method.as_str());
+    span.record(
+        "http.url",
+        format!(
+            "{}{}",
+            request.url.origin().ascii_serialization(),
+            context.path
+        )
+        .as_str(),
+    );
+    span.record("http.request_content_length", length);
+
+    #[cfg(feature = "opentelemetry")]
+    inject_context(&span, &mut request.headers);
+}
+
+pub(crate) fn record_response(response: &TransportResponse) {
+    let span = tracing::Span::current();
+    span.record("http.status_code", response.status.as_u16());
+    if let Some(length) = response
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+    {
+        span.record("http.response_content_length", length);
+    }
+    if response.status.is_client_error() || response.status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    } else {
+        span.record("otel.status_code", "OK");
+    }
+}
+
+pub(crate) fn record_error(error: &Error) {
+    let span = tracing::Span::current();
+    span.record("otel.status_code", "ERROR");
+    if let Some(status) = error.status().map(|status: StatusCode| status.as_u16()) {
+        span.record("http.status_code", status);
+    }
+    tracing::debug!(error = %error, "request failed");
+}
+
+#[cfg(feature = "opentelemetry")]
+fn inject_context(span: &tracing::Span, headers: &mut reqwest::header::HeaderMap) {
+    use opentelemetry::propagation::Injector;
+    use reqwest::header::{HeaderName, HeaderValue};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+    impl Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(key.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+
+    let context = span.context();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+// Keeps the fields of every closed span so tests can check what the service macro recorded.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+struct SpanRecorder {
+    spans: std::sync::Arc<std::sync::Mutex<Vec<(&'static str, SpanFields)>>>,
+}
+
+#[cfg(test)]
+type SpanFields = std::collections::BTreeMap<String, String>;
+
+#[cfg(test)]
+impl SpanRecorder {
+    fn fields(&self, name: &str) -> SpanFields {
+        let spans = self.spans.lock().unwrap();
+        let (_, fields) = spans
+            .iter()
+            .find(|(span, _)| *span == name)
+            .unwrap_or_else(|| panic!("no span named {}", name));
+        fields.clone()
+    }
+}
+
+#[cfg(test)]
+struct FieldVisitor<'a>(&'a mut SpanFields);
+
+#[cfg(test)]
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+#[cfg(test)]
+impl<S> tracing_subscriber::Layer<S> for SpanRecorder
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut fields = SpanFields::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        ctx.span(id).unwrap().extensions_mut().insert(fields);
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let span = ctx.span(id).unwrap();
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+
+    fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let span = ctx.span(&id).unwrap();
+        let fields = span.extensions_mut().remove::<SpanFields>();
+        self.spans
+            .lock()
+            .unwrap()
+            .push((span.name(), fields.unwrap_or_default()));
+    }
+}
+
+#[tokio::test]
+async fn test_instrumented_service() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let recorder = SpanRecorder::default();
+    let subscriber = tracing_subscriber::registry().with(recorder.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({
+            "message": "Bucket with the requested ID could not be found.",
+            "code": 404,
+            "type": "storage_bucket_not_found",
+            "version": "1.2.0"
+        }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport)
+        .build()
+        .unwrap();
+    let error = StoragesService::get_bucket(&client, &BucketId::new("b1".into()))
+        .await
+        .unwrap_err();
+    assert!(error.is_not_found());
+
+    let fields = recorder.fields("StoragesService::get_bucket");
+    let field = |name: &str| fields.get(name).map(String::as_str);
+    assert_eq!(field("appwrite.project_id"), Some("project"));
+    assert_eq!(field("appwrite.bucket_id"), Some("b1"));
+    assert_eq!(field("otel.kind"), Some("client"));
+    assert_eq!(field("http.method"), Some("GET"));
+    assert_eq!(
+        field("http.url"),
+        Some("http://localhost/v1/storage/buckets/b1")
+    );
+    assert_eq!(field("http.request_content_length"), Some("0"));
+    assert_eq!(field("http.status_code"), Some("404"));
+    assert_eq!(field("otel.status_code"), Some("ERROR"));
+}
+
+#[cfg(feature = "opentelemetry")]
+#[tokio::test]
+async fn test_trace_context_propagation() {
+    use crate::{prelude::*, transport::MemoryTransport};
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+    let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("appwrite-test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 1, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    HealthService::get_http(&client).await.unwrap();
+
+    let requests = transport.requests();
+    let traceparent = requests[0].headers["traceparent"].to_str().unwrap();
+    // version-trace_id-span_id-flags, with a sampled span
+    let parts = traceparent.split('-').collect::<Vec<_>>();
+    assert_eq!(parts.len(), 4, "{}", traceparent);
+    assert_eq!(parts[0], "00");
+    assert_ne!(parts[1], "0".repeat(32));
+    assert_eq!(parts[3], "01");
+}
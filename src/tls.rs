@@ -0,0 +1,186 @@
+use reqwest::{tls, Certificate, ClientBuilder, Identity};
+
+use crate::error::Error;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+compile_error!("either the `native-tls` or the `rustls-tls` feature must be enabled");
+
+// With both features enabled, e.g. `rustls-tls` added without `default-features = false`,
+// native-tls is used. Cargo unifies features across the dependency graph, so this can't be an error.
+#[cfg(feature = "native-tls")]
+pub const BACKEND: &str = "native-tls";
+#[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+pub const BACKEND: &str = "rustls-tls";
+
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub accept_invalid_certs: bool,
+    pub built_in_root_certs: bool,
+    pub root_certificates: Vec<Vec<u8>>,
+    pub client_identity: Option<ClientIdentity>,
+    pub min_version: Option<tls::Version>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientIdentity {
+    pub certificate_pem: Vec<u8>,
+    pub private_key_pem: Vec<u8>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        TlsConfig {
+            accept_invalid_certs: false,
+            built_in_root_certs: true,
+            root_certificates: Vec::new(),
+            client_identity: None,
+            min_version: None,
+        }
+    }
+}
+
+impl ClientIdentity {
+    #[cfg(feature = "native-tls")]
+    fn to_identity(&self) -> Result<Identity, reqwest::Error> {
+        Identity::from_pkcs8_pem(&self.certificate_pem, &self.private_key_pem)
+    }
+
+    #[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+    fn to_identity(&self) -> Result<Identity, reqwest::Error> {
+        let mut pem = self.certificate_pem.clone();
+        pem.push(b'\n');
+        pem.extend_from_slice(&self.private_key_pem);
+        Identity::from_pem(&pem)
+    }
+}
+
+impl TlsConfig {
+    pub fn is_default(&self) -> bool {
+        !self.accept_invalid_certs
+            && self.built_in_root_certs
+            && self.root_certificates.is_empty()
+            && self.client_identity.is_none()
+            && self.min_version.is_none()
+    }
+
+    pub(crate) fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, Error> {
+        #[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+        {
+            builder = builder.use_rustls_tls();
+        }
+        builder = builder
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .tls_built_in_root_certs(self.built_in_root_certs);
+        for pem in self.root_certificates.iter() {
+            let certificate = Certificate::from_pem(pem).map_err(Error::FailedToCreateClient)?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(ref identity) = self.client_identity {
+            let identity = identity
+                .to_identity()
+                .map_err(Error::FailedToCreateClient)?;
+            builder = builder.identity(identity);
+        }
+        if let Some(version) = self.min_version {
+            builder = builder.min_tls_version(version);
+        }
+        Ok(builder)
+    }
+}
+
+#[test]
+fn test_invalid_root_certificate() {
+    let result = crate::client::AppWriteClient::builder("https://localhost/v1", "project")
+        .self_signed(true)
+        .add_root_certificate_pem(
+            b"-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydGlmaWNhdGU=\n-----END CERTIFICATE-----\n",
+        )
+        .build();
+    assert!(matches!(result, Err(Error::FailedToCreateClient(_))));
+
+    let result = crate::client::AppWriteClient::builder("https://localhost/v1", "project")
+        .self_signed(true)
+        .min_tls_version(tls::Version::TLS_1_2)
+        .build();
+    assert!(result.is_ok());
+
+    let result = crate::client::AppWriteClient::builder("https://localhost/v1", "project")
+        .transport(crate::transport::MemoryTransport::new())
+        .self_signed(true)
+        .build();
+    assert!(matches!(result, Err(Error::InvalidConfig { .. })));
+}
+
+// The certificates under tests/fixtures/tls come from a throwaway P-256 CA valid for 100 years:
+// `server.pem` is issued for 127.0.0.1 and `client.pem` for client auth.
+#[tokio::test]
+async fn test_root_certificate_and_identity() {
+    use crate::prelude::*;
+    use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode};
+    use std::io::{Read, Write};
+
+    let path = |name: &str| format!("{}/tests/fixtures/tls/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let fixture = |name: &str| std::fs::read(path(name)).unwrap();
+    let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server()).unwrap();
+    acceptor
+        .set_private_key_file(path("server-key.pem"), SslFiletype::PEM)
+        .unwrap();
+    acceptor
+        .set_certificate_chain_file(path("server.pem"))
+        .unwrap();
+    acceptor.set_ca_file(path("ca.pem")).unwrap();
+    // Only a client presenting a certificate signed by the CA gets through the handshake.
+    acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    let acceptor = acceptor.build();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = acceptor.accept(stream.unwrap()) else {
+                continue;
+            };
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let body = r#"{"ping":7,"status":"pass"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+
+    let endpoint = format!("https://127.0.0.1:{}/v1", port);
+    let client = AppWriteClient::builder(&endpoint, "project")
+        .add_root_certificate_pem(&fixture("ca.pem"))
+        .client_identity_pem(&fixture("client.pem"), &fixture("client-key.pem"))
+        .build()
+        .unwrap();
+    let health = HealthService::get_http(&client).await.unwrap();
+    assert_eq!(health.ping, 7);
+
+    let untrusted = AppWriteClient::builder(&endpoint, "project")
+        .client_identity_pem(&fixture("client.pem"), &fixture("client-key.pem"))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        HealthService::get_http(&untrusted).await,
+        Err(Error::Request(_))
+    ));
+
+    let anonymous = AppWriteClient::builder(&endpoint, "project")
+        .add_root_certificate_pem(&fixture("ca.pem"))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        HealthService::get_http(&anonymous).await,
+        Err(Error::Request(_))
+    ));
+}
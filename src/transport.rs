@@ -0,0 +1,293 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    multipart, Method, StatusCode,
+};
+use url::Url;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone)]
+pub struct MultipartFile {
+    pub key: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub data: Bytes,
+}
+
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    Empty,
+    Json(serde_json::Value),
+    Form(serde_json::Value),
+    Multipart {
+        fields: Vec<(String, String)>,
+        file: MultipartFile,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap<HeaderValue>,
+    pub body: RequestBody,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap<HeaderValue>,
+    pub body: reqwest::Body,
+}
+
+impl TransportResponse {
+    pub fn new<B: Into<reqwest::Body>>(status: StatusCode, body: B) -> Self {
+        TransportResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn json(status: StatusCode, value: &serde_json::Value) -> Self {
+        let mut response = TransportResponse::new(status, value.to_string());
+        response
+            .headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    }
+}
+
+impl From<TransportResponse> for reqwest::Response {
+    fn from(response: TransportResponse) -> Self {
+        let mut builder = http::Response::builder().status(response.status);
+        if let Some(headers) = builder.headers_mut() {
+            *headers = response.headers;
+        }
+        // Building can only fail on invalid parts, which are already typed here.
+        builder.body(response.body).unwrap().into()
+    }
+}
+
+pub trait Transport: fmt::Debug + Send + Sync {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let mut req = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(timeout) = request.timeout {
+                req = req.timeout(timeout);
+            }
+            let req = match request.body {
+                RequestBody::Empty => req,
+                RequestBody::Json(json) => req.json(&json),
+                RequestBody::Form(json) => req.form(&json),
+                RequestBody::Multipart { fields, file } => {
+                    let part = multipart::Part::stream(file.data)
+                        .file_name(file.file_name)
+                        .mime_str(&file.mime_type)?;
+                    let mut form = multipart::Form::new();
+                    for (key, value) in fields {
+                        form = form.text(key, value);
+                    }
+                    req.multipart(form.part(file.key, part))
+                }
+            };
+            let response = req.send().await?;
+            Ok(TransportResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.into(),
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+enum ScriptedResponse {
+    Response {
+        status: StatusCode,
+        headers: HeaderMap<HeaderValue>,
+        body: Bytes,
+    },
+    Error(String),
+}
+
+#[derive(Debug, Default)]
+struct MemoryTransportState {
+    responses: VecDeque<ScriptedResponse>,
+    requests: Vec<TransportRequest>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTransport {
+    state: Arc<Mutex<MemoryTransportState>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_response<B: Into<Bytes>>(
+        &self,
+        status: StatusCode,
+        headers: HeaderMap<HeaderValue>,
+        body: B,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .push_back(ScriptedResponse::Response {
+                status,
+                headers,
+                body: body.into(),
+            });
+    }
+
+    pub fn push_error<S: Into<String>>(&self, message: S) {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .push_back(ScriptedResponse::Error(message.into()));
+    }
+
+    pub fn push_json(&self, status: StatusCode, value: serde_json::Value) {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.push_response(status, headers, value.to_string());
+    }
+
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.state.lock().unwrap().responses.len()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let mut state = self.state.lock().unwrap();
+            state.requests.push(request);
+            match state.responses.pop_front().ok_or(Error::SendFailed)? {
+                ScriptedResponse::Response {
+                    status,
+                    headers,
+                    body,
+                } => Ok(TransportResponse {
+                    status,
+                    headers,
+                    body: body.into(),
+                }),
+                ScriptedResponse::Error(message) => Err(Error::Transport(message.into())),
+            }
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_memory_transport() {
+    use crate::prelude::*;
+
+    let transport = MemoryTransport::new();
+    transport.push_json(
+        StatusCode::OK,
+        serde_json::json!({ "ping": 12, "status": "pass" }),
+    );
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let health = HealthService::get_http(&client).await.unwrap();
+    assert_eq!(health.ping, 12);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(requests[0].url.as_str(), "http://localhost/v1/health");
+    assert_eq!(requests[0].headers["x-appwrite-project"], "project");
+}
+
+#[tokio::test]
+async fn test_memory_transport_chunk_upload() {
+    use crate::prelude::*;
+
+    let transport = MemoryTransport::new();
+    let file = serde_json::json!({
+        "$id": "file",
+        "bucketId": "bucket",
+        "$createdAt": "2023-01-01T00:00:00.000+00:00",
+        "$updatedAt": "2023-01-01T00:00:00.000+00:00",
+        "$permissions": [],
+        "name": "data.bin",
+        "signature": "",
+        "mimeType": "application/octet-stream",
+        "sizeOriginal": 10,
+        "chunksTotal": 3,
+        "chunksUploaded": 3,
+    });
+    for _ in 0..3 {
+        transport.push_json(StatusCode::OK, file.clone());
+    }
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .chunk_size(4)
+        .build()
+        .unwrap();
+    let input = InputFile::from_bytes(vec![0; 10], Some("data.bin".into()), None)
+        .await
+        .unwrap();
+    StoragesService::create_file(
+        &client,
+        &BucketId::new("bucket".into()),
+        &FileId::unique(),
+        input,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].headers.get("x-appwrite-id").is_none());
+    assert_eq!(requests[2].headers["x-appwrite-id"], "file");
+    match &requests[2].body {
+        RequestBody::Multipart { file, .. } => assert_eq!(file.data.len(), 2),
+        body => panic!("unexpected body: {:?}", body),
+    }
+}
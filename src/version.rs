@@ -0,0 +1,156 @@
+use std::{fmt, str::FromStr};
+
+use reqwest::header::HeaderValue;
+
+use crate::{client::AppWriteClient, error::Error, services::health::HealthService};
+
+// The server version the client talks to. The models describe the 1.2 response format, so
+// that's the newest format it asks for: newer servers down-convert their responses to it, older
+// ones answer in their own shape, which is read into the 1.2 models with renamed fields taken
+// under their 1.2 name. Asking a 1.4 server for 1.4 responses would only have the models drop
+// what 1.4 adds; `test_fixtures_from_other_versions` lists those fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServerVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ServerVersion {
+    pub const V1_1: ServerVersion = ServerVersion::new(1, 1, 0);
+    pub const V1_2: ServerVersion = ServerVersion::new(1, 2, 0);
+    pub const V1_3: ServerVersion = ServerVersion::new(1, 3, 0);
+    pub const V1_4: ServerVersion = ServerVersion::new(1, 4, 0);
+    pub const V1_5: ServerVersion = ServerVersion::new(1, 5, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        ServerVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    // Appwrite only changes response formats in minor releases.
+    pub fn response_format(&self) -> String {
+        let format = (*self).min(ServerVersion::V1_2);
+        format!("{}.{}.0", format.major, format.minor)
+    }
+
+    pub(crate) fn header_value(&self) -> HeaderValue {
+        HeaderValue::from_str(&self.response_format()).expect("version numbers are valid headers")
+    }
+}
+
+impl Default for ServerVersion {
+    fn default() -> Self {
+        ServerVersion::V1_2
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for ServerVersion {
+    type Err = Error;
+
+    // Accepts `1.2`, `1.2.1` and pre-releases like `1.5.0-rc.3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidServerVersion(s.to_string());
+        let release = s.trim().trim_start_matches('v');
+        let release = release.split(['-', '+']).next().unwrap_or_default();
+        let mut parts = release.split('.').map(|part| part.parse::<u32>());
+        let major = parts.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+        let minor = parts.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+        let patch = parts.next().unwrap_or(Ok(0)).map_err(|_| invalid())?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(ServerVersion::new(major, minor, patch))
+    }
+}
+
+impl AppWriteClient {
+    // Every error response carries the server version too, so servers that hide
+    // /health/version still give it away.
+    pub async fn detect_version(&self) -> Result<ServerVersion, Error> {
+        match HealthService::get_version(self).await {
+            Ok(version) => version.version.parse(),
+            Err(error) => match error.api_error() {
+                Some(response) if !response.version.is_empty() => response.version.parse(),
+                _ => Err(error),
+            },
+        }
+    }
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(
+        "1.2.0".parse::<ServerVersion>().unwrap(),
+        ServerVersion::V1_2
+    );
+    assert_eq!("1.1".parse::<ServerVersion>().unwrap(), ServerVersion::V1_1);
+    assert_eq!(
+        "1.5.0-rc.3".parse::<ServerVersion>().unwrap(),
+        ServerVersion::V1_5
+    );
+    assert_eq!(
+        "1.4.13".parse::<ServerVersion>().unwrap(),
+        ServerVersion::new(1, 4, 13)
+    );
+    assert!("latest".parse::<ServerVersion>().is_err());
+    assert!("1".parse::<ServerVersion>().is_err());
+    assert!("1.2.3.4".parse::<ServerVersion>().is_err());
+    assert!(ServerVersion::V1_1 < ServerVersion::new(1, 2, 1));
+    assert_eq!(ServerVersion::new(1, 1, 2).response_format(), "1.1.0");
+    assert_eq!(ServerVersion::new(1, 2, 1).response_format(), "1.2.0");
+    assert_eq!(ServerVersion::new(1, 4, 13).response_format(), "1.2.0");
+}
+
+#[tokio::test]
+async fn test_detect_version() {
+    use crate::transport::MemoryTransport;
+    use reqwest::StatusCode;
+
+    let transport = MemoryTransport::new();
+    let client = AppWriteClient::builder("http://localhost/v1", "project")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    transport.push_json(StatusCode::OK, serde_json::json!({ "version": "1.1.2" }));
+    assert_eq!(
+        client.detect_version().await.unwrap(),
+        ServerVersion::new(1, 1, 2)
+    );
+    transport.push_json(
+        StatusCode::UNAUTHORIZED,
+        serde_json::json!({
+            "message": "User (role: guests) missing scope (health.read)",
+            "code": 401,
+            "type": "general_unauthorized_scope",
+            "version": "1.4.3"
+        }),
+    );
+    assert_eq!(
+        client.detect_version().await.unwrap(),
+        ServerVersion::new(1, 4, 3)
+    );
+
+    let newer = client.with_server_version(ServerVersion::new(1, 4, 3));
+    transport.push_json(StatusCode::OK, serde_json::json!({ "version": "1.4.3" }));
+    newer.detect_version().await.unwrap();
+    let client = client.with_server_version(ServerVersion::new(1, 1, 2));
+    transport.push_json(StatusCode::OK, serde_json::json!({ "version": "1.1.2" }));
+    client.detect_version().await.unwrap();
+    let formats = transport
+        .requests()
+        .iter()
+        .map(|request| request.headers["x-appwrite-response-format"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(formats, ["1.2.0", "1.2.0", "1.2.0", "1.1.0"]);
+    assert_eq!(client.server_version(), ServerVersion::new(1, 1, 2));
+}